@@ -0,0 +1,309 @@
+// Parancssori NTAG kódoló a grafikus felület nélkül (szkriptekhez, gyártósori állomáshoz).
+// Ugyanazt az nfc-core könyvtárat használja, mint a Tauri alkalmazás; a kimenet --json-nal gépi feldolgozásra való.
+
+use clap::{Args, Parser, Subcommand};
+use nfc_core::command_error::CommandError;
+use nfc_core::nfc::{Backend, NfcCard, NfcError, NfcSession, Ntag216, ReaderSelector, Secret, WrittenPages};
+use serde_json::json;
+use std::process::ExitCode;
+use tracing::{debug, warn};
+use tracing_subscriber::EnvFilter;
+
+/// NTAG21x címkék olvasása és írása parancssorból
+#[derive(Parser)]
+#[command(name = "nfc-cli", version)]
+struct Cli {
+    /// Olvasó háttér: pcsc vagy libnfc
+    #[arg(long, global = true, default_value = "pcsc", value_parser = parse_backend)]
+    backend: Backend,
+    /// Olvasó név (vagy részlet), sorszám, illetve libnfc connstring; alapértelmezés: az első olvasó
+    #[arg(long, global = true)]
+    reader: Option<String>,
+    /// JSON kimenet (hiba esetén is, a kilépési kód 1)
+    #[arg(long, global = true)]
+    json: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Csatlakoztatott olvasók listázása
+    ListReaders,
+    /// NDEF URL olvasása
+    ReadUrl,
+    /// NDEF URL írása
+    WriteUrl {
+        url: String,
+        #[command(flatten)]
+        write: WriteOptions,
+    },
+    /// NDEF Text olvasása
+    ReadText,
+    /// NDEF Text írása
+    WriteText {
+        text: String,
+        /// Nyelvi kód (pl. hu, en)
+        #[arg(long, default_value = "hu")]
+        language: String,
+        #[command(flatten)]
+        write: WriteOptions,
+    },
+    /// Password védelem beállítása
+    SetPassword {
+        /// Password (8 hex karakter, pl. 12345678)
+        #[arg(value_parser = parse_hex::<4>)]
+        password: [u8; 4],
+        /// PACK (4 hex karakter, pl. 8080)
+        #[arg(value_parser = parse_hex::<2>)]
+        pack: [u8; 2],
+        /// AUTHLIM: 0 nincs korlát, 1-7 legfeljebb 2^n hibás próbálkozás
+        #[arg(long, default_value_t = 0)]
+        auth_limit: u8,
+    },
+    /// A teljes memória kiírása lapokként (UID-tól a konfigurációs lapokig)
+    Dump,
+}
+
+/// Író parancsok közös kapcsolói
+#[derive(Args)]
+struct WriteOptions {
+    /// A címke password-je (8 hex karakter), ha védett
+    #[arg(long, value_parser = parse_hex::<4>)]
+    password: Option<[u8; 4]>,
+    /// A várt PACK (4 hex karakter); ha megadod, a PWD_AUTH válaszát ellenőrizzük vele
+    #[arg(long, value_parser = parse_hex::<2>)]
+    pack: Option<[u8; 2]>,
+    /// Írás után visszaolvasás és ellenőrzés
+    #[arg(long)]
+    verify: bool,
+}
+
+/// Egy parancs eredménye: szöveg a felhasználónak, JSON az automatizálásnak
+struct Output {
+    text: String,
+    json: serde_json::Value,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_target(false)
+        .with_env_filter(EnvFilter::try_from_env("NFC_LOG").unwrap_or_else(|_| EnvFilter::new("warn")))
+        .init();
+
+    match run(&cli) {
+        Ok(output) => {
+            if cli.json {
+                println!("{}", output.json);
+            } else {
+                println!("{}", output.text);
+            }
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            if cli.json {
+                println!("{}", json!({ "error": error }));
+            } else {
+                eprintln!("Hiba: {}", error);
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: &Cli) -> Result<Output, CommandError> {
+    cli.backend.ensure_available()?;
+    let mut session = NfcSession::default();
+    session.set_backend(cli.backend, cli.reader.as_deref().map(reader_selector));
+
+    if let Command::ListReaders = cli.command {
+        let readers = session
+            .list_readers()
+            .map_err(|e| CommandError::from(e).context("Olvasók listázási hiba"))?;
+        let text = readers
+            .iter()
+            .enumerate()
+            .map(|(index, name)| format!("{}: {}", index, name))
+            .collect::<Vec<_>>()
+            .join("\n");
+        return Ok(Output {
+            text,
+            json: json!({ "readers": readers }),
+        });
+    }
+
+    if let Command::Dump = cli.command {
+        return dump(&Ntag216, &mut session);
+    }
+
+    let card = session
+        .card()
+        .map_err(|e| CommandError::from(e).context("Csatlakozási hiba"))?;
+    let ntag = Ntag216;
+    ensure_ntag(&ntag, card)?;
+
+    match &cli.command {
+        Command::ListReaders | Command::Dump => unreachable!("fent kezelt parancs"),
+        Command::ReadUrl => {
+            let url = ntag
+                .read_ndef(card)
+                .map_err(|e| CommandError::from(e).context("Olvasási hiba"))?
+                .ok_or_else(|| CommandError::new("no_ndef", "Nem található NDEF URL üzenet a címkén"))?;
+            Ok(Output {
+                json: json!({ "url": url }),
+                text: url,
+            })
+        }
+        Command::WriteUrl { url, write } => {
+            debug!(url = %url, password = ?write.password.as_ref().map(|p| Secret(p)), "URL írása");
+            let written = ntag
+                .write_ndef_url_with_password(card, url, write.password.as_ref(), write.pack.as_ref())
+                .map_err(|e| CommandError::from(e).context("Írási hiba"))?;
+            let verified = verify_write(&ntag, card, &written, write.verify)?;
+            Ok(Output {
+                text: format!("URL írva: {}", url),
+                json: json!({ "url": url, "start_page": written.start_page, "pages": written.page_count(), "verified": verified }),
+            })
+        }
+        Command::ReadText => {
+            let (text, language) = ntag
+                .read_ndef_text(card)
+                .map_err(|e| CommandError::from(e).context("Olvasási hiba"))?
+                .ok_or_else(|| CommandError::new("no_ndef", "Nem található NDEF Text üzenet a címkén"))?;
+            Ok(Output {
+                text: format!("[{}] {}", language, text),
+                json: json!({ "text": text, "language": language }),
+            })
+        }
+        Command::WriteText { text, language, write } => {
+            let written = ntag
+                .write_ndef_text_with_password(card, text, language, write.password.as_ref(), write.pack.as_ref())
+                .map_err(|e| CommandError::from(e).context("Írási hiba"))?;
+            let verified = verify_write(&ntag, card, &written, write.verify)?;
+            Ok(Output {
+                text: format!("Szöveg írva ({}): {}", language, text),
+                json: json!({ "text": text, "language": language, "start_page": written.start_page, "pages": written.page_count(), "verified": verified }),
+            })
+        }
+        Command::SetPassword { password, pack, auth_limit } => {
+            ntag.set_password(card, password, pack, *auth_limit)
+                .map_err(|e| CommandError::from(e).context("Password beállítási hiba"))?;
+            Ok(Output {
+                text: "Password védelem beállítva".to_string(),
+                json: json!({ "password_set": true, "auth_limit": auth_limit }),
+            })
+        }
+    }
+}
+
+/// A címke memóriája a 0. laptól a CFG1 lapig; FAST_READ-del, ha az olvasó továbbítja
+fn dump(ntag: &Ntag216, session: &mut NfcSession) -> Result<Output, CommandError> {
+    let card = session
+        .card()
+        .map_err(|e| CommandError::from(e).context("Csatlakozási hiba"))?;
+    ensure_ntag(ntag, card)?;
+    let uid = ntag
+        .read_uid(card)
+        .map_err(|e| CommandError::from(e).context("UID olvasási hiba"))?;
+    let chip = ntag
+        .detect_chip(card)
+        .map_err(|e| CommandError::from(e).context("Chip felismerési hiba"))?;
+    let last_page = chip.cfg1_page();
+
+    let data = match ntag.fast_read(card, 0, last_page) {
+        Ok(data) => data,
+        Err(e) => {
+            // NAK után a címke HALT állapotban van: a lapról lapra olvasás előtt újra kiválasztjuk
+            let card = if e.leaves_tag_halted() {
+                warn!("FAST_READ NAK ({}), újracsatlakozás után lapról lapra olvasunk", e);
+                session
+                    .reconnect()
+                    .map_err(|e| CommandError::from(e).context("Újracsatlakozási hiba"))?
+            } else {
+                warn!("FAST_READ nem sikerült ({}), lapról lapra olvasunk", e);
+                card
+            };
+            let mut data = Vec::new();
+            for page in 0..=last_page {
+                let block = ntag
+                    .read_block(card, page)
+                    .map_err(|e| CommandError::from(e).context(&format!("Block {} olvasási hiba", page)))?;
+                data.extend_from_slice(&block);
+            }
+            data
+        }
+    };
+
+    let pages: Vec<String> = data.chunks(4).map(to_hex).collect();
+    let text = format!(
+        "UID: {}\nChip: {:?}\n{}",
+        to_hex(&uid),
+        chip,
+        pages
+            .iter()
+            .enumerate()
+            .map(|(page, hex)| format!("{:03}: {}", page, hex))
+            .collect::<Vec<_>>()
+            .join("\n")
+    );
+    Ok(Output {
+        text,
+        json: json!({ "uid": to_hex(&uid), "chip": chip, "pages": pages }),
+    })
+}
+
+fn ensure_ntag(ntag: &Ntag216, card: &dyn NfcCard) -> Result<(), CommandError> {
+    match ntag.check_type(card) {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(NfcError::NotNtag.into()),
+        Err(e) => Err(CommandError::from(e).context("Címke ellenőrzési hiba")),
+    }
+}
+
+/// Visszaolvasás és összehasonlítás, ha kérték; az ellenőrzött lapok száma
+fn verify_write(ntag: &Ntag216, card: &dyn NfcCard, written: &WrittenPages, verify: bool) -> Result<Option<usize>, CommandError> {
+    if !verify {
+        return Ok(None);
+    }
+    let report = ntag
+        .verify_written(card, written)
+        .map_err(|e| CommandError::from(e).context("Visszaolvasási hiba"))?
+        .ensure_ok()
+        .map_err(|e| CommandError::from(e).context("Ellenőrzési hiba"))?;
+    Ok(Some(report.pages_checked))
+}
+
+/// Szám: sorszám, egyébként olvasó név (részlet) vagy connstring
+fn reader_selector(value: &str) -> ReaderSelector {
+    match value.parse() {
+        Ok(index) => ReaderSelector::Index(index),
+        Err(_) => ReaderSelector::Name(value.to_string()),
+    }
+}
+
+fn parse_backend(value: &str) -> Result<Backend, String> {
+    match value {
+        "pcsc" => Ok(Backend::Pcsc),
+        "libnfc" => Ok(Backend::Libnfc),
+        other => Err(format!("Ismeretlen háttér: {} (pcsc vagy libnfc)", other)),
+    }
+}
+
+/// Pontosan N byte hex formátumban (szóközök megengedettek)
+fn parse_hex<const N: usize>(value: &str) -> Result<[u8; N], String> {
+    let digits: String = value.chars().filter(|c| !c.is_whitespace()).collect();
+    if digits.len() != N * 2 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("Pontosan {} hex karakter kell ({} byte)", N * 2, N));
+    }
+    let mut bytes = [0u8; N];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&digits[i * 2..i * 2 + 2], 16).map_err(|e| e.to_string())?;
+    }
+    Ok(bytes)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,70 @@
+use serde::Serialize;
+use std::fmt;
+
+use crate::nfc::NfcError;
+
+/// A Tauri parancsok hibája a frontendnek
+///
+/// JSON-ban `{ code, message, details }`: a `code` gépi hibakód (NFC hibánál az
+/// `NfcError` kódja, pl. "auth_required"), a `message` a megjeleníthető szöveg,
+/// a `details` NFC hibánál a típusos hiba mezői (status word, PACK, eltérő lapok, ...).
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandError {
+    pub code: &'static str,
+    pub message: String,
+    pub details: Option<NfcError>,
+}
+
+impl CommandError {
+    pub fn new(code: &'static str, message: impl Into<String>) -> Self {
+        CommandError {
+            code,
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    /// A művelet megnevezése az üzenet elé (pl. "Írási hiba: ..."); a kód nem változik
+    pub fn context(mut self, context: &str) -> Self {
+        self.message = format!("{}: {}", context, self.message);
+        self
+    }
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl From<NfcError> for CommandError {
+    fn from(error: NfcError) -> Self {
+        CommandError {
+            code: error.code(),
+            message: error.to_string(),
+            details: Some(error),
+        }
+    }
+}
+
+/// anyhow hiba: ha a láncban van `NfcError`, annak a kódját kapja, a legkülső üzenettel
+impl From<anyhow::Error> for CommandError {
+    fn from(error: anyhow::Error) -> Self {
+        let message = error.to_string();
+        match error.chain().find_map(|cause| cause.downcast_ref::<NfcError>()) {
+            Some(nfc_error) => CommandError {
+                code: nfc_error.code(),
+                message,
+                details: Some(nfc_error.clone()),
+            },
+            None => CommandError::new("error", message),
+        }
+    }
+}
+
+/// Validációs hibák (a parancsok bemenetének ellenőrzése)
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        CommandError::new("invalid_argument", message)
+    }
+}
@@ -0,0 +1,5 @@
+// NFC kezelés a grafikus felülettől függetlenül: a Tauri alkalmazás és az nfc-cli közös kódja.
+// Tauri függőség nélkül fordul, így a parancssori eszköz webview nélkül is építhető.
+
+pub mod command_error;
+pub mod nfc;
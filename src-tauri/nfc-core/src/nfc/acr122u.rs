@@ -0,0 +1,213 @@
+use serde::{Deserialize, Serialize};
+
+use super::error::{NfcError, Result};
+use super::transport::{self, ApduResponse, RawTransmit};
+
+/// LED állapot vezérlés (FF 00 40 P2): minden LED-nél a végső állapot, a villogás kezdő
+/// állapota, és hogy az adott LED-et egyáltalán módosítjuk-e
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LedControl {
+    pub final_red: bool,
+    pub final_green: bool,
+    /// A piros LED végső állapotát beállítjuk (különben marad a jelenlegi)
+    pub update_red: bool,
+    pub update_green: bool,
+    pub initial_blink_red: bool,
+    pub initial_blink_green: bool,
+    /// A piros LED villog a T1/T2 ütemezés szerint
+    pub blink_red: bool,
+    pub blink_green: bool,
+}
+
+impl LedControl {
+    pub fn to_byte(self) -> u8 {
+        [
+            self.final_red,
+            self.final_green,
+            self.update_red,
+            self.update_green,
+            self.initial_blink_red,
+            self.initial_blink_green,
+            self.blink_red,
+            self.blink_green,
+        ]
+        .iter()
+        .enumerate()
+        .fold(0, |byte, (bit, &set)| if set { byte | (1 << bit) } else { byte })
+    }
+}
+
+/// A buzzer a villogás melyik fázisában szól
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Buzzer {
+    Off = 0x00,
+    /// A kezdő villogási állapot alatt (T1)
+    DuringT1 = 0x01,
+    /// A váltott villogási állapot alatt (T2)
+    DuringT2 = 0x02,
+    Both = 0x03,
+}
+
+/// Villogás ütemezés: T1 és T2 100 ms egységekben, ismétlések száma
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlinkTiming {
+    pub t1: u8,
+    pub t2: u8,
+    pub repetitions: u8,
+}
+
+/// Visszajelzés egy művelet eredményéről
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feedback {
+    /// Zöld villogás, rövid sípolás
+    Success,
+    /// Piros villogás, hosszú sípolás
+    Failure,
+}
+
+/// PICC működési paraméterek (FF 00 50 / FF 00 51): automatikus címke keresés és
+/// a keresett címke típusok
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PiccParameters {
+    pub auto_polling: bool,
+    /// ATS automatikus lekérése ISO 14443-4 címkéknél
+    pub auto_ats: bool,
+    /// Keresési időköz: true = 250 ms, false = 500 ms
+    pub fast_polling: bool,
+    pub felica_424: bool,
+    pub felica_212: bool,
+    pub topaz: bool,
+    pub iso14443b: bool,
+    pub iso14443a: bool,
+}
+
+impl PiccParameters {
+    pub fn from_byte(byte: u8) -> Self {
+        PiccParameters {
+            auto_polling: byte & 0x80 != 0,
+            auto_ats: byte & 0x40 != 0,
+            fast_polling: byte & 0x20 != 0,
+            felica_424: byte & 0x10 != 0,
+            felica_212: byte & 0x08 != 0,
+            topaz: byte & 0x04 != 0,
+            iso14443b: byte & 0x02 != 0,
+            iso14443a: byte & 0x01 != 0,
+        }
+    }
+
+    pub fn to_byte(self) -> u8 {
+        (self.auto_polling as u8) << 7
+            | (self.auto_ats as u8) << 6
+            | (self.fast_polling as u8) << 5
+            | (self.felica_424 as u8) << 4
+            | (self.felica_212 as u8) << 3
+            | (self.topaz as u8) << 2
+            | (self.iso14443b as u8) << 1
+            | self.iso14443a as u8
+    }
+}
+
+/// LED és buzzer vezérlés; a válasz az új LED állapot (bit 0: piros, bit 1: zöld)
+pub fn set_led_buzzer<T: RawTransmit + ?Sized>(card: &T, led: LedControl, timing: BlinkTiming, buzzer: Buzzer) -> Result<u8> {
+    let apdu = [
+        0xFF,
+        0x00,
+        0x40,
+        led.to_byte(),
+        0x04,
+        timing.t1,
+        timing.t2,
+        timing.repetitions,
+        buzzer as u8,
+    ];
+    reader_status(escape(card, &apdu)?)
+}
+
+/// Sikeres / sikertelen művelet jelzése villogással és sípolással; utána a LED-ek kialszanak
+pub fn signal<T: RawTransmit + ?Sized>(card: &T, feedback: Feedback) -> Result<()> {
+    let (led, timing, buzzer) = match feedback {
+        Feedback::Success => (
+            LedControl {
+                update_red: true,
+                update_green: true,
+                initial_blink_green: true,
+                blink_green: true,
+                ..LedControl::default()
+            },
+            BlinkTiming { t1: 1, t2: 1, repetitions: 2 },
+            Buzzer::DuringT1,
+        ),
+        Feedback::Failure => (
+            LedControl {
+                update_red: true,
+                update_green: true,
+                initial_blink_red: true,
+                blink_red: true,
+                ..LedControl::default()
+            },
+            BlinkTiming { t1: 5, t2: 2, repetitions: 3 },
+            Buzzer::DuringT1,
+        ),
+    };
+    set_led_buzzer(card, led, timing, buzzer).map(|_| ())
+}
+
+/// A buzzer megszólal-e címke felismeréskor (FF 00 52). Batch kódolásnál érdemes
+/// kikapcsolni, hogy csak az eredmény jelzése hallatszon.
+pub fn set_detection_buzzer<T: RawTransmit + ?Sized>(card: &T, enabled: bool) -> Result<()> {
+    let apdu = [0xFF, 0x00, 0x52, if enabled { 0xFF } else { 0x00 }, 0x00];
+    reader_status(escape(card, &apdu)?).map(|_| ())
+}
+
+/// Antenna (RF mező) be- és kikapcsolása: PN532 RFConfiguration, az InCommunicateThru-val
+/// azonos Direct Transmit kereten
+pub fn set_antenna<T: RawTransmit + ?Sized>(card: &T, on: bool) -> Result<()> {
+    let apdu = [0xFF, 0x00, 0x00, 0x00, 0x04, 0xD4, 0x32, 0x01, on as u8];
+    let data = escape(card, &apdu)?.into_data()?;
+    if data.starts_with(&[0xD5, 0x33]) {
+        Ok(())
+    } else {
+        Err(NfcError::invalid_response(format!("Érvénytelen RFConfiguration válasz: {:02X?}", data)))
+    }
+}
+
+/// Firmware verzió (FF 00 48 00 00), pl. "ACR122U207". A válasz ASCII szöveg status word nélkül.
+pub fn firmware_version<T: RawTransmit + ?Sized>(card: &T) -> Result<String> {
+    let raw = card.transmit_raw(&[0xFF, 0x00, 0x48, 0x00, 0x00])?;
+    let text: String = raw
+        .iter()
+        .take_while(|b| b.is_ascii_graphic())
+        .map(|&b| b as char)
+        .collect();
+    if text.starts_with("ACR") {
+        Ok(text)
+    } else {
+        Err(NfcError::invalid_response(format!("Nem ACS firmware válasz: {:02X?}", raw)))
+    }
+}
+
+pub fn picc_parameters<T: RawTransmit + ?Sized>(card: &T) -> Result<PiccParameters> {
+    reader_status(escape(card, &[0xFF, 0x00, 0x50, 0x00, 0x00])?).map(PiccParameters::from_byte)
+}
+
+/// PICC paraméterek beállítása; a válasz az olvasó által elfogadott új érték
+pub fn set_picc_parameters<T: RawTransmit + ?Sized>(card: &T, parameters: PiccParameters) -> Result<PiccParameters> {
+    let apdu = [0xFF, 0x00, 0x51, parameters.to_byte(), 0x00];
+    reader_status(escape(card, &apdu)?).map(PiccParameters::from_byte)
+}
+
+/// ACR122U pseudo-APDU (escape parancs): az olvasónak szól, nem a címkének, ezért a
+/// status word ("90 xx") az olvasó állapotát hordozza, nem a szokásos 90 00
+fn escape<T: RawTransmit + ?Sized>(card: &T, apdu: &[u8]) -> Result<ApduResponse> {
+    Ok(transport::transmit_with_retry(card, apdu, &transport::retry_policy())?)
+}
+
+/// "90 xx" válasz: az xx byte az olvasó állapota
+fn reader_status(response: ApduResponse) -> Result<u8> {
+    if response.sw1 == 0x90 {
+        Ok(response.sw2)
+    } else {
+        Err(NfcError::status_word(response.sw1, response.sw2))
+    }
+}
@@ -0,0 +1,141 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use super::card_trait::NfcCard;
+use super::error::NfcError;
+use super::reader::{NfcReader, ReaderSelector};
+use super::reader_libnfc;
+
+/// Olvasó háttér: futás közben választható (beállítás + Tauri parancs)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Backend {
+    /// PC/SC (pcscd / WinSCard) az olvasó CCID driverével
+    #[default]
+    Pcsc,
+    /// libnfc közvetlenül az olvasó chipjével (pl. ha a pcscd nem kezeli az olvasót)
+    Libnfc,
+}
+
+impl Backend {
+    /// Az ezen a gépen használható hátterek
+    pub fn available() -> Vec<Backend> {
+        [Backend::Pcsc, Backend::Libnfc]
+            .into_iter()
+            .filter(|backend| backend.ensure_available().is_ok())
+            .collect()
+    }
+
+    /// Használható-e a háttér. Mindkét háttér be van fordítva, de a libnfc-t futás közben
+    /// töltjük be: ha nincs telepítve, `BackendUnavailable` az okkal.
+    pub fn ensure_available(self) -> std::result::Result<(), NfcError> {
+        match self {
+            Backend::Pcsc => Ok(()),
+            Backend::Libnfc => reader_libnfc::ensure_loaded(),
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Backend::Pcsc => "PC/SC",
+            Backend::Libnfc => "libnfc",
+        }
+    }
+
+    /// Az olvasó háttér megnyitása
+    pub fn open(self) -> Result<Box<dyn Reader>> {
+        match self {
+            Backend::Pcsc => Ok(Box::new(NfcReader::new()?)),
+            Backend::Libnfc => Ok(Box::new(libnfc::LibnfcBackend::new()?)),
+        }
+    }
+}
+
+/// Olvasó háttér közös felülete: olvasók listázása és csatlakozás a címkéhez
+///
+/// A csatlakoztatott címkén (`NfcCard`) történik az adatküldés és az állapot lekérdezés,
+/// így az NTAG216 implementáció háttértől függetlenül működik.
+pub trait Reader: Send {
+    /// Használható-e még a háttér (pl. pcscd újraindulás után a PC/SC kontextus nem)
+    fn is_valid(&self) -> bool {
+        true
+    }
+
+    fn list_readers(&self) -> Result<Vec<String>>;
+
+    /// Csatlakozás a kiválasztott olvasón lévő címkéhez (None: az első olvasó)
+    fn connect(&self, selector: Option<&ReaderSelector>) -> Result<Box<dyn NfcCard>>;
+
+    /// Újracsatlakozás, miután az előző kapcsolatot lezártuk: a HALT állapotú címkét is
+    /// újra kiválasztja. PC/SC-n a lezárás (SCardDisconnect, ResetCard) már újraindítja a címkét.
+    fn reconnect(&self, selector: Option<&ReaderSelector>) -> Result<Box<dyn NfcCard>> {
+        self.connect(selector)
+    }
+}
+
+impl Reader for NfcReader {
+    fn is_valid(&self) -> bool {
+        NfcReader::is_valid(self)
+    }
+
+    fn list_readers(&self) -> Result<Vec<String>> {
+        NfcReader::list_readers(self)
+    }
+
+    fn connect(&self, selector: Option<&ReaderSelector>) -> Result<Box<dyn NfcCard>> {
+        Ok(Box::new(NfcReader::connect(self, selector)?))
+    }
+}
+
+mod libnfc {
+    use anyhow::Result;
+    use std::sync::{Arc, Mutex};
+
+    use super::Reader;
+    use crate::nfc::card_trait::{LibnfcCardWrapper, NfcCard};
+    use crate::nfc::error::NfcError;
+    use crate::nfc::reader::ReaderSelector;
+    use crate::nfc::reader_libnfc::{self, LibnfcReader};
+
+    /// libnfc háttér: egy kontextus, egyszerre egy megnyitott eszközzel. A csatlakoztatott
+    /// címke ugyanazt az eszközt használja, ezért közös (Mutex mögötti) olvasón osztoznak.
+    pub struct LibnfcBackend {
+        reader: Arc<Mutex<LibnfcReader>>,
+    }
+
+    impl LibnfcBackend {
+        pub fn new() -> Result<Self> {
+            reader_libnfc::ensure_loaded()?;
+            let reader = LibnfcReader::new().map_err(|e| NfcError::BackendUnavailable {
+                reason: format!("A libnfc nem inicializálható: {}", e),
+            })?;
+            Ok(LibnfcBackend {
+                reader: Arc::new(Mutex::new(reader)),
+            })
+        }
+    }
+
+    impl Reader for LibnfcBackend {
+        fn list_readers(&self) -> Result<Vec<String>> {
+            self.reader.lock().unwrap().list_readers()
+        }
+
+        fn connect(&self, selector: Option<&ReaderSelector>) -> Result<Box<dyn NfcCard>> {
+            let target = {
+                let mut reader = self.reader.lock().unwrap();
+                if reader.selection() != selector {
+                    reader.restore_selection(selector.cloned());
+                }
+                reader.connect()?;
+                reader.select_target()?
+            };
+            Ok(Box::new(LibnfcCardWrapper::new(self.reader.clone(), target)))
+        }
+
+        fn reconnect(&self, selector: Option<&ReaderSelector>) -> Result<Box<dyn NfcCard>> {
+            // A HALT állapotú címke csak WUPA-ra válaszol: az RF mező ki-be kapcsolásával ébresztjük
+            self.reader.lock().unwrap().reset_field()?;
+            self.connect(selector)
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use tracing::debug;
+
+use super::backend::Backend;
+use super::driver::ReaderDriver;
+use super::error::Result;
+use super::reader_libnfc::LibnfcReader;
+use super::transport::{retry_policy, transmit_with_retry, RawTransmit, TransmitError, TransmitErrorKind};
+
+/// Trait az NFC kártya kommunikációhoz
+/// Ez lehetővé teszi, hogy az NTAG216 implementáció működjön PC/SC és libnfc egyaránt
+///
+/// Minden háttér PC/SC pseudo-APDU-kat fogad (FF CA, FF B0, FF D6, Direct Transmit);
+/// a libnfc háttér ezeket natív címke parancsokra fordítja.
+pub trait NfcCard: RawTransmit + Send {
+    /// APDU parancs küldése a címkének
+    fn transmit(&self, apdu: &[u8]) -> Result<Vec<u8>> {
+        let response = transmit_with_retry(self, apdu, &retry_policy())?;
+        // Visszaadjuk a választ status byte-ok nélkül
+        response.into_data()
+    }
+
+    /// A háttér, amelyen keresztül a címkével kommunikálunk
+    fn backend(&self) -> Backend;
+
+    /// Annak az olvasónak a neve, amelyen a címke van
+    fn reader_name(&self) -> Option<String>;
+
+    /// Él-e még a kapcsolat (a címke az olvasón van)
+    fn is_present(&self) -> bool;
+
+    /// ISO 14443-A azonosítók, ha a háttér a kiválasztáskor megadja (libnfc)
+    fn target(&self) -> Option<Iso14443aTarget> {
+        None
+    }
+
+    /// Az olvasó driver, ha a kapcsolathoz már felismertük (az `NfcSession` csatlakozáskor
+    /// egyszer azonosítja, hogy ne kelljen minden natív parancs előtt újra lekérdezni)
+    fn driver(&self) -> Option<ReaderDriver> {
+        None
+    }
+}
+
+/// A kiválasztott ISO 14443-A címke azonosítói
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Iso14443aTarget {
+    pub uid: Vec<u8>,
+    pub atqa: [u8; 2],
+    pub sak: u8,
+    /// ISO 14443-4 címkéknél az ATS, NTAG-nél üres
+    pub ats: Vec<u8>,
+}
+
+// PC/SC Card implementáció
+impl NfcCard for pcsc::Card {
+    fn backend(&self) -> Backend {
+        Backend::Pcsc
+    }
+
+    fn reader_name(&self) -> Option<String> {
+        let status = self.status2_owned().ok()?;
+        status
+            .reader_names()
+            .first()
+            .map(|name| name.to_string_lossy().to_string())
+    }
+
+    fn is_present(&self) -> bool {
+        self.status2_owned().is_ok()
+    }
+}
+
+// libnfc wrapper implementáció
+pub struct LibnfcCardWrapper {
+    reader: Arc<Mutex<LibnfcReader>>,
+    target: Iso14443aTarget,
+}
+
+impl LibnfcCardWrapper {
+    pub fn new(reader: Arc<Mutex<LibnfcReader>>, target: Iso14443aTarget) -> Self {
+        Self { reader, target }
+    }
+}
+
+/// Status word-ök a pseudo-APDU emulációhoz
+const SW_OK: [u8; 2] = [0x90, 0x00];
+/// A címke NAK-ot adott vagy nem válaszolt (mint az ACS olvasóknál)
+const SW_NO_RESPONSE: [u8; 2] = [0x63, 0x00];
+const SW_INS_NOT_SUPPORTED: [u8; 2] = [0x6D, 0x00];
+
+/// A libnfc közvetlenül a címkével beszél, ezért a PC/SC pseudo-APDU-kat natív Type 2
+/// parancsokra fordítjuk (READ 0x30, WRITE 0xA2), a választ pedig status word-del egészítjük ki
+///
+/// A kommunikációs hibák (RF timeout, CRC hiba, eltávolított címke) osztályozott
+/// `TransmitError`-ként mennek tovább, hogy az újrapróbálás kezelje őket; 63 00 csak a címke
+/// tényleges elutasítását (NAK) jelzi, illetve natív parancsnál azt, hogy nem válaszolt.
+impl RawTransmit for LibnfcCardWrapper {
+    fn transmit_raw(&self, apdu: &[u8]) -> Result<Vec<u8>, TransmitError> {
+        let mut reader = self.reader.lock().unwrap();
+        match apdu {
+            // GET DATA: UID (a kiválasztáskor kapott érték)
+            [0xFF, 0xCA, 0x00, 0x00, ..] => Ok(with_status(&self.target.uid, SW_OK)),
+            // READ BINARY: Le byte a P2 laptól; egy READ 4 lapot (16 byte) ad vissza
+            [0xFF, 0xB0, 0x00, page, le] => {
+                let len = if *le == 0 { 256 } else { *le as usize };
+                let mut data = Vec::with_capacity(len + 16);
+                let mut page = *page;
+                while data.len() < len {
+                    let Some(block) = tag_command(&mut reader, &[0x30, page])? else {
+                        return Ok(SW_NO_RESPONSE.to_vec());
+                    };
+                    if block.len() != 16 {
+                        return Err(TransmitError::new(
+                            TransmitErrorKind::Protocol,
+                            format!("Váratlan READ válasz: {} byte (16 helyett)", block.len()),
+                        ));
+                    }
+                    data.extend_from_slice(&block);
+                    page = page.wrapping_add(4);
+                }
+                data.truncate(len);
+                Ok(with_status(&data, SW_OK))
+            }
+            // UPDATE BINARY: egy lap (4 byte) írása
+            [0xFF, 0xD6, 0x00, page, 0x04, data @ ..] if data.len() == 4 => {
+                let mut command = vec![0xA2, *page];
+                command.extend_from_slice(data);
+                match tag_command(&mut reader, &command)? {
+                    Some(_) => Ok(SW_OK.to_vec()),
+                    None => Ok(SW_NO_RESPONSE.to_vec()),
+                }
+            }
+            // Direct Transmit: a parancs változatlanul a címkének, a NAK-ot a driver dekódolja
+            [0xFF, 0x00, 0x00, 0x00, lc, command @ ..] if command.len() == *lc as usize => {
+                match reader.transmit_native(command)? {
+                    Some(response) => Ok(with_status(&response, SW_OK)),
+                    None => Ok(SW_NO_RESPONSE.to_vec()),
+                }
+            }
+            _ => Ok(SW_INS_NOT_SUPPORTED.to_vec()),
+        }
+    }
+}
+
+impl NfcCard for LibnfcCardWrapper {
+    fn backend(&self) -> Backend {
+        Backend::Libnfc
+    }
+
+    fn reader_name(&self) -> Option<String> {
+        self.reader.lock().unwrap().device_name()
+    }
+
+    fn is_present(&self) -> bool {
+        self.reader.lock().unwrap().is_target_present()
+    }
+
+    fn target(&self) -> Option<Iso14443aTarget> {
+        Some(self.target.clone())
+    }
+}
+
+fn with_status(data: &[u8], sw: [u8; 2]) -> Vec<u8> {
+    let mut response = data.to_vec();
+    response.extend_from_slice(&sw);
+    response
+}
+
+/// READ / WRITE a címkének; None, ha a címke elutasította (4 bites NAK, vagy a libnfc
+/// NFC_EMFCAUTHFAIL kódja: password védett lap). Minden más hiba változatlanul megy tovább.
+fn tag_command(reader: &mut LibnfcReader, command: &[u8]) -> Result<Option<Vec<u8>>, TransmitError> {
+    match reader.transmit_raw(command) {
+        Ok(response) if matches!(response.as_slice(), [nak] if *nak <= 0x0F && *nak != 0x0A) => {
+            debug!("NAK a címkétől: 0x{:X}", response[0]);
+            Ok(None)
+        }
+        Ok(response) => Ok(Some(response)),
+        Err(e) if e.kind == TransmitErrorKind::AuthFailure => {
+            debug!("libnfc: {}", e);
+            Ok(None)
+        }
+        Err(e) => Err(e),
+    }
+}
@@ -0,0 +1,104 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use super::error::{NfcError, Result};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Domain separation címke, hogy a mester kulcsot más célra is biztonságosan lehessen használni
+const DIVERSIFICATION_LABEL: &[u8] = b"NTAG216-PWD-PACK";
+
+/// Mester kulcs profil a címkénkénti (diverzifikált) password-okhoz
+///
+/// PWD || PACK = HMAC-SHA256(mester kulcs, címke || UID) első 6 byte-ja.
+/// Így egy címke password-jének kiszivárgása nem veszélyezteti a többi címkét.
+#[derive(Clone)]
+pub struct KeyProfile {
+    pub name: String,
+    master_key: Vec<u8>,
+}
+
+/// UID-ból származtatott password és PACK
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiversifiedKey {
+    pub password: [u8; 4],
+    pub pack: [u8; 2],
+}
+
+impl KeyProfile {
+    pub fn new(name: &str, master_key: Vec<u8>) -> Result<Self> {
+        if master_key.len() < 16 {
+            return Err(NfcError::invalid_argument(format!(
+                "A mester kulcs legalább 16 byte kell legyen! (kapott: {})",
+                master_key.len()
+            )));
+        }
+
+        Ok(KeyProfile {
+            name: name.to_string(),
+            master_key,
+        })
+    }
+
+    /// PWD és PACK származtatása a címke UID-jából
+    pub fn derive(&self, uid: &[u8]) -> DiversifiedKey {
+        let mut mac = HmacSha256::new_from_slice(&self.master_key)
+            .expect("HMAC bármilyen kulcshosszt elfogad");
+        mac.update(DIVERSIFICATION_LABEL);
+        mac.update(uid);
+        let digest = mac.finalize().into_bytes();
+
+        DiversifiedKey {
+            password: [digest[0], digest[1], digest[2], digest[3]],
+            pack: [digest[4], digest[5]],
+        }
+    }
+}
+
+// A mester kulcs soha ne kerüljön logba
+impl std::fmt::Debug for KeyProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyProfile")
+            .field("name", &self.name)
+            .field("master_key", &"<rejtett>")
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UID: [u8; 7] = [0x04, 0xA1, 0xB2, 0xC3, 0xD4, 0xE5, 0x80];
+
+    fn profile(master_key: Vec<u8>) -> KeyProfile {
+        KeyProfile::new("teszt", master_key).unwrap()
+    }
+
+    #[test]
+    fn derive_matches_hmac_sha256_vector() {
+        // HMAC-SHA256(00..0F, "NTAG216-PWD-PACK" || UID) első 6 byte-ja: EA FB B4 92 EA AA
+        let key = profile((0..16).collect()).derive(&UID);
+        assert_eq!(key.password, [0xEA, 0xFB, 0xB4, 0x92]);
+        assert_eq!(key.pack, [0xEA, 0xAA]);
+    }
+
+    #[test]
+    fn derive_depends_on_uid_and_master_key() {
+        let profile_a = profile((0..16).collect());
+        let profile_b = profile((1..17).collect());
+        let mut other_uid = UID;
+        other_uid[6] ^= 0x01;
+        assert_eq!(profile_a.derive(&UID), profile_a.derive(&UID));
+        assert_ne!(profile_a.derive(&UID), profile_a.derive(&other_uid));
+        assert_ne!(profile_a.derive(&UID), profile_b.derive(&UID));
+    }
+
+    #[test]
+    fn short_master_key_and_debug_output() {
+        let error = KeyProfile::new("rövid", vec![0u8; 15]).unwrap_err();
+        assert_eq!(error.code(), "invalid_argument");
+        let debug = format!("{:?}", profile(vec![0xAB; 16]));
+        assert!(!debug.contains("171") && !debug.contains("AB"), "{}", debug);
+    }
+}
@@ -0,0 +1,214 @@
+use serde::Serialize;
+use tracing::debug;
+
+use super::acr122u;
+use super::backend::Backend;
+use super::card_trait::NfcCard;
+use super::error::{NfcError, Result};
+use super::status_word;
+use super::transparent::{self, TransparentSession};
+use super::transport::{self, ApduResponse, RawTransmit, RetryPolicy};
+
+/// Olvasó specifikus driver: a natív címke parancsok (pl. PWD_AUTH, GET_VERSION)
+/// becsomagolása az olvasó által értett pseudo-APDU-ba
+///
+/// A PC/SC szabványos tároló kártya parancsai (FF B0 / FF D6 / FF CA) minden olvasón
+/// működnek, a natív ISO 14443-3 parancsok továbbítása viszont olvasónként eltér.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReaderDriver {
+    /// ACS ACR122U (PN532): Direct Transmit + InCommunicateThru
+    Acr122u,
+    /// ACS ACR1252U (és a vele kompatibilis ACS olvasók): Direct Transmit
+    Acr1252u,
+    /// ACS ACR1255U-J1 (Bluetooth): Direct Transmit
+    Acr1255u,
+    /// Identiv uTrust (3700 F, 4701 F, ...): PC/SC Part 3 transparent session
+    IdentivUtrust,
+    /// HID OMNIKEY 5022: PC/SC Part 3 transparent session
+    Omnikey5022,
+    /// Ismeretlen olvasó: a szabványos PC/SC Part 3 transparent session-t próbáljuk
+    GenericPart3,
+    /// libnfc háttér: a Direct Transmit keretet a háttér natív parancsként küldi tovább
+    Libnfc,
+}
+
+/// A natív parancs keretezése
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NativeFraming {
+    /// FF 00 00 00 Lc D4 42 [parancs]: a PN532 InCommunicateThru parancsa
+    InCommunicateThru,
+    /// FF 00 00 00 Lc [parancs]: ACS Direct Transmit
+    DirectTransmit,
+    /// FF C2 00 01 Lc 95 L [parancs]: PC/SC 2.01 Part 3 Transparent Exchange,
+    /// a Start / End Session parancsok között
+    TransparentSession,
+}
+
+/// A címke válasza egy natív parancsra
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NativeResponse {
+    /// A címke adattal válaszolt
+    Data(Vec<u8>),
+    /// 4 bites NAK a címkétől (Type 2: 0x0, 0x1, 0x4, 0x5)
+    Nak(u8),
+    /// A címke nem válaszolt (timeout vagy RF hiba az olvasó szerint)
+    NoResponse { reason: String },
+}
+
+impl NativeResponse {
+    /// A címke válasz adata; NAK vagy hiányzó válasz esetén hiba
+    pub fn into_data(self) -> Result<Vec<u8>> {
+        match self {
+            NativeResponse::Data(data) => Ok(data),
+            NativeResponse::Nak(nak) => Err(NfcError::TagNak {
+                nak,
+                description: status_word::describe_type2_ack(nak).to_string(),
+            }),
+            NativeResponse::NoResponse { reason } => Err(NfcError::NoTagResponse { reason }),
+        }
+    }
+}
+
+impl ReaderDriver {
+    /// Felismerés a PC/SC olvasó név alapján
+    pub fn from_name(name: &str) -> Self {
+        let name = name.to_uppercase();
+        if name.contains("ACR122") {
+            ReaderDriver::Acr122u
+        } else if name.contains("ACR1255") {
+            ReaderDriver::Acr1255u
+        } else if name.contains("ACR1252") || name.contains("ACS ACR12") || name.contains("ACS ACR15") {
+            ReaderDriver::Acr1252u
+        } else if name.contains("IDENTIV") || name.contains("UTRUST") {
+            ReaderDriver::IdentivUtrust
+        } else if name.contains("OMNIKEY") && name.contains("5022") {
+            ReaderDriver::Omnikey5022
+        } else {
+            ReaderDriver::GenericPart3
+        }
+    }
+
+    /// A csatlakoztatott címke olvasójának drivere: a kapcsolathoz már felismert driver,
+    /// ha van (`NfcCard::driver`), különben felismerés (`probe`)
+    pub fn identify(card: &dyn NfcCard) -> Self {
+        card.driver().unwrap_or_else(|| ReaderDriver::probe(card))
+    }
+
+    /// Felismerés a csatlakoztatott címke olvasója alapján. Az ismeretlen nevű ACS olvasóknál
+    /// (pl. egyes Windows driverek "ACS CCID USB Reader" néven jelentik) a firmware verziót
+    /// is lekérdezzük: az ACR122U erre "ACR122U2xx" szöveggel válaszol. Kapcsolatonként
+    /// egyszer hívjuk (`NfcSession`), mert a lekérdezés egy plusz APDU az olvasónak.
+    pub fn probe(card: &dyn NfcCard) -> Self {
+        if card.backend() == Backend::Libnfc {
+            return ReaderDriver::Libnfc;
+        }
+        let Some(name) = card.reader_name() else {
+            return ReaderDriver::GenericPart3;
+        };
+        let driver = ReaderDriver::from_name(&name);
+        if driver == ReaderDriver::GenericPart3 && name.to_uppercase().contains("ACS") {
+            if let Ok(firmware) = acr122u::firmware_version(card) {
+                debug!("Firmware: {}", firmware);
+                if firmware.starts_with("ACR122") {
+                    return ReaderDriver::Acr122u;
+                }
+                return ReaderDriver::Acr1252u;
+            }
+        }
+        driver
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ReaderDriver::Acr122u => "ACS ACR122U",
+            ReaderDriver::Acr1252u => "ACS ACR1252U",
+            ReaderDriver::Acr1255u => "ACS ACR1255U",
+            ReaderDriver::IdentivUtrust => "Identiv uTrust",
+            ReaderDriver::Omnikey5022 => "HID OMNIKEY 5022",
+            ReaderDriver::GenericPart3 => "Általános PC/SC Part 3",
+            ReaderDriver::Libnfc => "libnfc",
+        }
+    }
+
+    pub fn framing(self) -> NativeFraming {
+        match self {
+            ReaderDriver::Acr122u => NativeFraming::InCommunicateThru,
+            ReaderDriver::Acr1252u | ReaderDriver::Acr1255u | ReaderDriver::Libnfc => NativeFraming::DirectTransmit,
+            ReaderDriver::IdentivUtrust | ReaderDriver::Omnikey5022 | ReaderDriver::GenericPart3 => {
+                NativeFraming::TransparentSession
+            }
+        }
+    }
+
+    /// Natív parancs küldése a címkének és a válasz kicsomagolása
+    ///
+    /// A `policy` a hívó döntése: pl. PWD_AUTH-ot nem szabad újrapróbálni.
+    pub fn transmit_native<T: RawTransmit + ?Sized>(self, card: &T, command: &[u8], policy: &RetryPolicy) -> Result<NativeResponse> {
+        let framing = self.framing();
+        if framing == NativeFraming::TransparentSession {
+            // A session csak erre az egy parancsra nyílik, eldobáskor zárul
+            return TransparentSession::start(card)?.transceive(command, policy);
+        }
+        let apdu = framing.wrap(command);
+        let response = transport::transmit_with_retry(card, &apdu, policy)?;
+        framing.unwrap(response)
+    }
+}
+
+impl NativeFraming {
+    /// Natív parancs becsomagolása pseudo-APDU-ba
+    pub fn wrap(self, command: &[u8]) -> Vec<u8> {
+        match self {
+            NativeFraming::InCommunicateThru => {
+                let mut apdu = vec![0xFF, 0x00, 0x00, 0x00, (command.len() + 2) as u8, 0xD4, 0x42];
+                apdu.extend_from_slice(command);
+                apdu
+            }
+            NativeFraming::DirectTransmit => {
+                let mut apdu = vec![0xFF, 0x00, 0x00, 0x00, command.len() as u8];
+                apdu.extend_from_slice(command);
+                apdu
+            }
+            NativeFraming::TransparentSession => transparent::transceive_apdu(command),
+        }
+    }
+
+    /// A pseudo-APDU válaszából a címke válasza
+    pub fn unwrap(self, response: ApduResponse) -> Result<NativeResponse> {
+        match self {
+            NativeFraming::InCommunicateThru => {
+                let data = response.into_data()?;
+                // Válasz: D5 43 [Status] [címke válasz...]
+                if data.len() < 3 || data[0] != 0xD5 || data[1] != 0x43 {
+                    return Err(NfcError::invalid_response(format!("Érvénytelen PN532 válasz: {:02X?}", data)));
+                }
+                if data[2] != 0x00 {
+                    return Ok(NativeResponse::NoResponse {
+                        reason: format!("PN532 status 0x{:02X}: {}", data[2], status_word::describe_pn533_status(data[2])),
+                    });
+                }
+                Ok(tag_response(&data[3..]))
+            }
+            NativeFraming::DirectTransmit => {
+                // Az ACS olvasók 63 00-val jelzik, ha a címke nem válaszolt
+                if response.sw1 == 0x63 {
+                    return Ok(NativeResponse::NoResponse {
+                        reason: status_word::describe(response.sw1, response.sw2),
+                    });
+                }
+                Ok(tag_response(&response.into_data()?))
+            }
+            NativeFraming::TransparentSession => transparent::parse_exchange(&response.into_data()?),
+        }
+    }
+}
+
+/// Egyetlen byte, ami nem ACK: a címke 4 bites NAK válasza
+pub(crate) fn tag_response(data: &[u8]) -> NativeResponse {
+    match data {
+        [nak] if *nak <= 0x0F && *nak != 0x0A => NativeResponse::Nak(*nak),
+        _ => NativeResponse::Data(data.to_vec()),
+    }
+}
@@ -0,0 +1,211 @@
+use serde::Serialize;
+use std::fmt;
+
+use super::ntag216::NtagChip;
+use super::status_word;
+use super::transport::{classify_status, TransmitError, TransmitErrorKind};
+use super::verify::VerifyReport;
+
+pub type Result<T, E = NfcError> = std::result::Result<T, E>;
+
+/// Típusos NFC hiba
+///
+/// A frontend a `code` mező alapján dönt (pl. password bekérése), nem a szöveg alapján.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "code", rename_all = "snake_case")]
+pub enum NfcError {
+    /// A címke hibát jelző status word-del válaszolt
+    StatusWord {
+        sw1: u8,
+        sw2: u8,
+        kind: TransmitErrorKind,
+        /// A status word jelentése (ISO 7816 / PC/SC Part 3 / olvasó specifikus)
+        description: String,
+    },
+    /// A címke NAK-kal válaszolt egy natív parancsra
+    TagNak { nak: u8, description: String },
+    /// A címke nem válaszolt a natív parancsra (timeout / RF hiba az olvasó szerint)
+    NoTagResponse { reason: String },
+    /// Kommunikációs hiba az olvasóval vagy a címkével
+    Transmit {
+        kind: TransmitErrorKind,
+        message: String,
+    },
+    /// A művelethez password kell, de nem adtak meg (vagy nem lett elfogadva)
+    AuthRequired,
+    /// A címke elutasította a password-t (PWD_AUTH NAK)
+    WrongPassword,
+    /// A password elfogadva, de a PACK nem egyezik a várttal
+    PackMismatch { expected: [u8; 2], received: [u8; 2] },
+    /// Az olvasó nem tudja továbbítani a natív PWD_AUTH parancsot
+    AuthUnsupported,
+    /// Nem NDEF formázott / nem NTAG címke
+    NotNtag,
+    /// Ismeretlen NTAG21x változat (CC méret byte)
+    UnknownChip { cc: [u8; 4] },
+    /// A címke típusa eltér a zárolási tervben szereplőtől
+    ChipMismatch { expected: NtagChip, actual: NtagChip },
+    /// Az adat nem fér el (byte-ban)
+    OutOfCapacity { size: usize, max: usize },
+    /// A címke konfigurációja zárolva van
+    TagLocked,
+    /// Érvénytelen bemenet (block szám, nyelv kód, zárolási tartomány, ...)
+    InvalidArgument { reason: String },
+    /// Nincs (vagy nem található a kiválasztott) olvasó
+    ReaderMissing { reason: String },
+    /// A kiválasztott olvasó háttér (PC/SC / libnfc) nem érhető el ebben a buildben
+    /// vagy nem inicializálható
+    BackendUnavailable { reason: String },
+    /// Nincs címke az olvasón
+    NoTag,
+    /// Értelmezhetetlen válasz a címkétől / olvasótól
+    InvalidResponse { reason: String },
+    /// A visszaolvasott tartalom eltér a kiírttól
+    VerificationFailed { report: VerifyReport },
+}
+
+impl NfcError {
+    /// Hiba egy nem 9000 status word-ből
+    pub fn status_word(sw1: u8, sw2: u8) -> Self {
+        NfcError::StatusWord {
+            sw1,
+            sw2,
+            kind: classify_status(sw1, sw2),
+            description: status_word::describe(sw1, sw2),
+        }
+    }
+
+    /// A címke NAK-ot adott vagy nem válaszolt egy natív parancsra: ilyenkor HALT állapotba
+    /// kerül, és a következő parancs előtt újra ki kell választani (`NfcSession::reconnect`)
+    pub fn leaves_tag_halted(&self) -> bool {
+        matches!(self, NfcError::TagNak { .. } | NfcError::NoTagResponse { .. })
+    }
+
+    pub fn invalid_argument(reason: impl Into<String>) -> Self {
+        NfcError::InvalidArgument { reason: reason.into() }
+    }
+
+    pub fn invalid_response(reason: impl Into<String>) -> Self {
+        NfcError::InvalidResponse { reason: reason.into() }
+    }
+
+    pub fn reader_missing(reason: impl Into<String>) -> Self {
+        NfcError::ReaderMissing { reason: reason.into() }
+    }
+
+    /// Gépi hibakód (megegyezik a JSON `code` mezőjével)
+    pub fn code(&self) -> &'static str {
+        match self {
+            NfcError::StatusWord { .. } => "status_word",
+            NfcError::TagNak { .. } => "tag_nak",
+            NfcError::NoTagResponse { .. } => "no_tag_response",
+            NfcError::Transmit { .. } => "transmit",
+            NfcError::AuthRequired => "auth_required",
+            NfcError::WrongPassword => "wrong_password",
+            NfcError::PackMismatch { .. } => "pack_mismatch",
+            NfcError::AuthUnsupported => "auth_unsupported",
+            NfcError::NotNtag => "not_ntag",
+            NfcError::UnknownChip { .. } => "unknown_chip",
+            NfcError::ChipMismatch { .. } => "chip_mismatch",
+            NfcError::OutOfCapacity { .. } => "out_of_capacity",
+            NfcError::TagLocked => "tag_locked",
+            NfcError::InvalidArgument { .. } => "invalid_argument",
+            NfcError::ReaderMissing { .. } => "reader_missing",
+            NfcError::BackendUnavailable { .. } => "backend_unavailable",
+            NfcError::NoTag => "no_tag",
+            NfcError::InvalidResponse { .. } => "invalid_response",
+            NfcError::VerificationFailed { .. } => "verification_failed",
+        }
+    }
+
+    /// A címke password védelem miatt utasította el a műveletet
+    pub fn is_auth_failure(&self) -> bool {
+        matches!(
+            self,
+            NfcError::AuthRequired
+                | NfcError::StatusWord {
+                    kind: TransmitErrorKind::AuthFailure,
+                    ..
+                }
+        )
+    }
+}
+
+impl fmt::Display for NfcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NfcError::StatusWord { sw1, sw2, description, .. } => write!(
+                f,
+                "NFC címke hiba: {} (SW1=0x{:02X}, SW2=0x{:02X})",
+                description, sw1, sw2
+            ),
+            NfcError::TagNak { nak, description } => write!(f, "{} (0x{:X})", description, nak),
+            NfcError::NoTagResponse { reason } => write!(f, "A címke nem válaszolt: {}", reason),
+            NfcError::Transmit { message, .. } => write!(f, "{}", message),
+            NfcError::AuthRequired => {
+                write!(f, "Password védelem aktív, de nincs password megadva. Add meg a password-t!")
+            }
+            NfcError::WrongPassword => {
+                write!(f, "Hibás password: a címke elutasította a PWD_AUTH parancsot")
+            }
+            NfcError::PackMismatch { expected, received } => write!(
+                f,
+                "PACK eltérés: várt {:02X?}, kapott {:02X?}. A címke nem a várt címke!",
+                expected, received
+            ),
+            NfcError::AuthUnsupported => {
+                write!(f, "Az olvasó nem támogatja a PWD_AUTH parancsot PC/SC-n keresztül")
+            }
+            NfcError::NotNtag => write!(f, "Ez nem egy NTAG216 címke"),
+            NfcError::UnknownChip { cc } => {
+                write!(f, "Ismeretlen címke: CC={:02X?} (nem NTAG213/215/216)", cc)
+            }
+            NfcError::ChipMismatch { expected, actual } => write!(
+                f,
+                "A címke típusa ({:?}) eltér a tervben szereplőtől ({:?})",
+                actual, expected
+            ),
+            NfcError::OutOfCapacity { size, max } => {
+                write!(f, "Az adat túl nagy: {} byte (max {} byte)", size, max)
+            }
+            NfcError::TagLocked => {
+                write!(f, "A címke zárolva van! Nem lehet módosítani a konfigurációt.")
+            }
+            NfcError::InvalidArgument { reason }
+            | NfcError::ReaderMissing { reason }
+            | NfcError::BackendUnavailable { reason }
+            | NfcError::InvalidResponse { reason } => write!(f, "{}", reason),
+            NfcError::NoTag => write!(
+                f,
+                "Nem sikerült csatlakozni az NFC címkéhez. Kérlek helyezd a címkét az olvasóra."
+            ),
+            NfcError::VerificationFailed { report } => {
+                write!(
+                    f,
+                    "A visszaolvasott tartalom eltér: {} / {} lap",
+                    report.mismatches.len(),
+                    report.pages_checked
+                )?;
+                for mismatch in &report.mismatches {
+                    write!(
+                        f,
+                        "\n  Lap {}: várt {:02X?}, olvasott {:02X?} (eltérő byte-ok: {:?})",
+                        mismatch.page, mismatch.expected, mismatch.actual, mismatch.differing_bytes
+                    )?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for NfcError {}
+
+impl From<TransmitError> for NfcError {
+    fn from(error: TransmitError) -> Self {
+        NfcError::Transmit {
+            kind: error.kind,
+            message: error.message,
+        }
+    }
+}
@@ -0,0 +1,262 @@
+use serde::Serialize;
+
+use super::error::{NfcError, Result};
+use super::ntag216::NtagChip;
+
+/// Zárolási kérés (NFC Forum Type 2 lock bitek)
+#[derive(Debug, Clone, Default)]
+pub struct LockRequest {
+    /// Zárolandó lap tartományok (első és utolsó lap, inkluzív)
+    pub ranges: Vec<(u8, u8)>,
+    /// CFGLCK: a felhasználói konfiguráció (CFG0/CFG1) végleges zárolása
+    pub lock_config: bool,
+    /// CC access byte 0x0F-ra állítása, hogy az NDEF olvasók read-only címkének lássák
+    pub cc_read_only: bool,
+    /// Block-locking bitek: a lock biteket is befagyasztják
+    pub freeze_lock_bits: bool,
+}
+
+impl LockRequest {
+    /// A teljes címke végleges read-only módja
+    pub fn read_only(chip: NtagChip) -> Self {
+        LockRequest {
+            ranges: vec![(3, chip.user_end())],
+            lock_config: true,
+            cc_read_only: true,
+            freeze_lock_bits: true,
+        }
+    }
+}
+
+/// Zárolási terv (dry-run eredménye): pontosan ezek a byte-ok kerülnek a címkére
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LockPlan {
+    pub chip: NtagChip,
+    /// Statikus lock byte-ok (2. lap, 2-3. byte)
+    pub static_lock: [u8; 2],
+    /// Dinamikus lock byte-ok (a felhasználói memória utáni lap)
+    pub dynamic_lock: [u8; 3],
+    pub cfglck: bool,
+    pub cc_read_only: bool,
+    /// Lapok, amelyek véglegesen read-only-vá válnak.
+    /// A zárolás granularitása miatt bővebb lehet a kért tartományoknál!
+    pub locked_pages: Vec<u8>,
+}
+
+impl LockPlan {
+    /// Van-e bármi, amit írni kell
+    pub fn is_empty(&self) -> bool {
+        self.static_lock == [0, 0] && self.dynamic_lock == [0, 0, 0] && !self.cfglck && !self.cc_read_only
+    }
+
+    /// Olvasható összefoglaló a dry-run-hoz
+    pub fn report(&self) -> String {
+        let mut lines = vec![format!("Chip: {:?}", self.chip)];
+        if self.locked_pages.is_empty() {
+            lines.push("Véglegesen read-only lapok: nincs".to_string());
+        } else {
+            lines.push(format!("Véglegesen read-only lapok: {}", format_ranges(&self.locked_pages)));
+        }
+        lines.push(format!("Statikus lock byte-ok (2. lap): {:02X?}", self.static_lock));
+        lines.push(format!(
+            "Dinamikus lock byte-ok ({}. lap): {:02X?}",
+            self.chip.dynamic_lock_page(),
+            self.dynamic_lock
+        ));
+        lines.push(format!("CFGLCK: {}", if self.cfglck { "Igen" } else { "Nem" }));
+        lines.push(format!("CC access byte = 0x0F: {}", if self.cc_read_only { "Igen" } else { "Nem" }));
+        lines.join("\n")
+    }
+}
+
+/// Zárolási terv készítése. Nem kommunikál a címkével.
+pub fn plan_lock(chip: NtagChip, request: &LockRequest) -> Result<LockPlan> {
+    let user_end = chip.user_end();
+    let pages_per_bit = chip.pages_per_dynamic_lock_bit();
+
+    let mut static_lock = [0u8; 2];
+    let mut dynamic_lock = [0u8; 3];
+
+    for &(first, last) in &request.ranges {
+        if first > last {
+            return Err(NfcError::invalid_argument(format!("Érvénytelen tartomány: {}-{}", first, last)));
+        }
+        if first < 3 {
+            return Err(NfcError::invalid_argument("A 0-2. lapok (UID, lock byte-ok) nem zárolhatók"));
+        }
+        if last > user_end {
+            return Err(NfcError::invalid_argument(format!(
+                "A(z) {}. lap kívül esik a zárolható memórián (3-{}). A konfigurációhoz használd a CFGLCK-t!",
+                last, user_end
+            )));
+        }
+
+        for page in first..=last {
+            match page {
+                3 => static_lock[0] |= 0x08, // L-CC
+                4..=7 => static_lock[0] |= 1 << page, // L4-L7: Lock0 bit 4-7
+                8..=15 => static_lock[1] |= 1 << (page - 8), // L8-L15: Lock1 bit 0-7
+                _ => {
+                    let bit = (page - 16) / pages_per_bit;
+                    dynamic_lock[(bit / 8) as usize] |= 1 << (bit % 8);
+                }
+            }
+        }
+    }
+
+    if request.freeze_lock_bits {
+        // Statikus block-locking bitek: BL-CC, BL 9-4, BL 15-10
+        if static_lock[0] & 0x08 != 0 {
+            static_lock[0] |= 0x01;
+        }
+        if static_lock[0] & 0xF0 != 0 || static_lock[1] & 0x03 != 0 {
+            static_lock[0] |= 0x02;
+        }
+        if static_lock[1] & 0xFC != 0 {
+            static_lock[0] |= 0x04;
+        }
+
+        // Dinamikus block-locking bitek a 3. byte-ban
+        let bits_per_block_lock = chip.lock_bits_per_block_lock_bit();
+        for bit in 0..chip.dynamic_lock_bits() {
+            if dynamic_lock[(bit / 8) as usize] & (1 << (bit % 8)) != 0 {
+                dynamic_lock[2] |= 1 << (bit / bits_per_block_lock);
+            }
+        }
+    }
+
+    let mut locked_pages = Vec::new();
+    if static_lock[0] & 0x08 != 0 {
+        locked_pages.push(3);
+    }
+    for page in 4..=15u8 {
+        let locked = if page < 8 {
+            static_lock[0] & (1 << page) != 0
+        } else {
+            static_lock[1] & (1 << (page - 8)) != 0
+        };
+        if locked {
+            locked_pages.push(page);
+        }
+    }
+    for bit in 0..chip.dynamic_lock_bits() {
+        if dynamic_lock[(bit / 8) as usize] & (1 << (bit % 8)) != 0 {
+            let first = 16 + bit * pages_per_bit;
+            let last = (first + pages_per_bit - 1).min(user_end);
+            locked_pages.extend(first..=last);
+        }
+    }
+
+    Ok(LockPlan {
+        chip,
+        static_lock,
+        dynamic_lock,
+        cfglck: request.lock_config,
+        cc_read_only: request.cc_read_only,
+        locked_pages,
+    })
+}
+
+/// Lap lista tömörítése tartományokká, pl. "3-15, 16-47"
+fn format_ranges(pages: &[u8]) -> String {
+    let mut ranges: Vec<String> = Vec::new();
+    let mut iter = pages.iter().copied();
+    let Some(mut start) = iter.next() else {
+        return String::new();
+    };
+    let mut end = start;
+
+    for page in iter {
+        if page == end + 1 {
+            end = page;
+            continue;
+        }
+        ranges.push(if start == end { start.to_string() } else { format!("{}-{}", start, end) });
+        start = page;
+        end = page;
+    }
+    ranges.push(if start == end { start.to_string() } else { format!("{}-{}", start, end) });
+
+    ranges.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(ranges: &[(u8, u8)]) -> LockRequest {
+        LockRequest {
+            ranges: ranges.to_vec(),
+            ..LockRequest::default()
+        }
+    }
+
+    #[test]
+    fn static_lock_bits_for_pages_3_to_15() {
+        let plan = plan_lock(NtagChip::Ntag216, &request(&[(3, 15)])).unwrap();
+        assert_eq!(plan.static_lock, [0xF8, 0xFF]);
+        assert_eq!(plan.dynamic_lock, [0, 0, 0]);
+        assert_eq!(plan.locked_pages, (3..=15).collect::<Vec<u8>>());
+
+        let frozen = plan_lock(
+            NtagChip::Ntag216,
+            &LockRequest {
+                freeze_lock_bits: true,
+                ..request(&[(3, 15)])
+            },
+        )
+        .unwrap();
+        // BL-CC, BL 9-4, BL 15-10
+        assert_eq!(frozen.static_lock, [0xFF, 0xFF]);
+    }
+
+    #[test]
+    fn dynamic_lock_granularity_widens_the_range() {
+        // NTAG216: egy dinamikus lock bit 16 lapot zárol
+        let plan = plan_lock(NtagChip::Ntag216, &request(&[(20, 20)])).unwrap();
+        assert_eq!(plan.dynamic_lock, [0x01, 0, 0]);
+        assert_eq!(plan.locked_pages, (16..=31).collect::<Vec<u8>>());
+        assert_eq!(format_ranges(&plan.locked_pages), "16-31");
+
+        // NTAG213: 2 lap / bit
+        let plan = plan_lock(NtagChip::Ntag213, &request(&[(19, 19)])).unwrap();
+        assert_eq!(plan.dynamic_lock, [0x02, 0, 0]);
+        assert_eq!(plan.locked_pages, vec![18, 19]);
+    }
+
+    #[test]
+    fn read_only_locks_all_user_pages() {
+        let plan = plan_lock(NtagChip::Ntag216, &LockRequest::read_only(NtagChip::Ntag216)).unwrap();
+        assert_eq!(plan.static_lock, [0xFF, 0xFF]);
+        // 210 lap / 16 = 14 lock bit, 2 lock bit / block-locking bit = 7 block-locking bit
+        assert_eq!(plan.dynamic_lock, [0xFF, 0x3F, 0x7F]);
+        assert_eq!(plan.locked_pages, (3..=0xE1).collect::<Vec<u8>>());
+        assert!(plan.cfglck && plan.cc_read_only);
+
+        let plan = plan_lock(NtagChip::Ntag213, &LockRequest::read_only(NtagChip::Ntag213)).unwrap();
+        // 24 lap / 2 = 12 lock bit, 4 lock bit / block-locking bit = 3 block-locking bit
+        assert_eq!(plan.dynamic_lock, [0xFF, 0x0F, 0x07]);
+        assert_eq!(plan.locked_pages.last(), Some(&0x27));
+    }
+
+    #[test]
+    fn invalid_ranges_are_rejected() {
+        for ranges in [[(10, 5)], [(2, 5)], [(4, 0xE2)]] {
+            assert!(
+                matches!(plan_lock(NtagChip::Ntag216, &request(&ranges)), Err(NfcError::InvalidArgument { .. })),
+                "{:?}",
+                ranges
+            );
+        }
+        // NTAG213-on a 0x28 már a dinamikus lock lap
+        assert!(plan_lock(NtagChip::Ntag213, &request(&[(4, 0x28)])).is_err());
+    }
+
+    #[test]
+    fn empty_request_plans_nothing() {
+        let plan = plan_lock(NtagChip::Ntag215, &LockRequest::default()).unwrap();
+        assert!(plan.is_empty());
+        assert!(plan.locked_pages.is_empty());
+        assert!(!plan_lock(NtagChip::Ntag215, &request(&[(4, 4)])).unwrap().is_empty());
+    }
+}
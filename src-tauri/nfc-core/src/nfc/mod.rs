@@ -0,0 +1,32 @@
+pub mod ntag216;
+pub mod reader;
+pub mod reader_libnfc;
+pub mod session;
+pub mod status_word;
+pub mod acr122u;
+pub mod backend;
+pub mod card_trait;
+pub mod diversify;
+pub mod driver;
+pub mod error;
+pub mod lock;
+pub mod monitor;
+pub mod secret;
+pub mod trace;
+pub mod transparent;
+pub mod transport;
+pub mod verify;
+
+pub use ntag216::Ntag216;
+pub use lock::{LockPlan, LockRequest};
+pub use diversify::{DiversifiedKey, KeyProfile};
+pub use error::NfcError;
+pub use reader::ReaderSelector;
+pub use monitor::{CardMonitor, MonitorEvent};
+pub use session::NfcSession;
+pub use transport::RetryPolicy;
+pub use verify::WrittenPages;
+pub use secret::Secret;
+pub use backend::Backend;
+pub use card_trait::NfcCard;
+
@@ -0,0 +1,200 @@
+use anyhow::{Context, Result};
+use pcsc::{Context as PcscContext, Disposition, Protocols, ReaderState, Scope, ShareMode, State, PNP_NOTIFICATION};
+use serde::Serialize;
+use std::ffi::CString;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+use super::ntag216::{NtagChip, Ntag216};
+
+/// Ilyen gyakran nézzük meg, kérték-e a monitor leállítását
+const POLL_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Olvasó és címke események
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MonitorEvent {
+    ReaderAttached {
+        reader: String,
+    },
+    ReaderDetached {
+        reader: String,
+    },
+    CardInserted {
+        reader: String,
+        /// ATR hex formátumban
+        atr: String,
+        /// UID hex formátumban (ha kiolvasható)
+        uid: Option<String>,
+        /// Felismert chip (ha NTAG213/215/216)
+        chip: Option<NtagChip>,
+    },
+    CardRemoved {
+        reader: String,
+    },
+}
+
+impl MonitorEvent {
+    /// Az esemény neve a frontend felé
+    pub fn name(&self) -> &'static str {
+        match self {
+            MonitorEvent::ReaderAttached { .. } => "nfc-reader-attached",
+            MonitorEvent::ReaderDetached { .. } => "nfc-reader-detached",
+            MonitorEvent::CardInserted { .. } => "nfc-card-inserted",
+            MonitorEvent::CardRemoved { .. } => "nfc-card-removed",
+        }
+    }
+}
+
+/// Háttér monitor a PC/SC `get_status_change` alapján
+///
+/// Saját PC/SC kontextust használ, így nem blokkolja a parancsok munkamenetét.
+/// A leállítás a `stop()` hívással vagy a monitor eldobásával történik.
+pub struct CardMonitor {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl CardMonitor {
+    /// Monitor indítása; minden eseménynél meghívja a callback-et a háttérszálon
+    pub fn start<F>(mut on_event: F) -> Result<Self>
+    where
+        F: FnMut(MonitorEvent) + Send + 'static,
+    {
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+
+        let handle = thread::Builder::new()
+            .name("nfc-monitor".to_string())
+            .spawn(move || {
+                debug!("Címke monitor elindítva");
+                while !thread_stop.load(Ordering::Relaxed) {
+                    // A pcscd újraindulása után új kontextussal folytatjuk
+                    if let Err(e) = run(&thread_stop, &mut on_event) {
+                        warn!("Címke monitor hiba: {}", e);
+                        thread::sleep(Duration::from_secs(2));
+                    }
+                }
+                debug!("Címke monitor leállítva");
+            })
+            .context("Nem sikerült elindítani a címke monitort")?;
+
+        Ok(CardMonitor {
+            stop,
+            handle: Some(handle),
+        })
+    }
+
+    pub fn stop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for CardMonitor {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Figyelő ciklus egy PC/SC kontextussal, amíg le nem állítják vagy hiba nem történik
+fn run<F: FnMut(MonitorEvent)>(stop: &AtomicBool, on_event: &mut F) -> Result<()> {
+    let ctx = PcscContext::establish(Scope::User)
+        .context("Nem sikerült csatlakozni a PC/SC szolgáltatáshoz")?;
+    let mut buffer = [0u8; 2048];
+    let mut states = vec![ReaderState::new(PNP_NOTIFICATION(), State::UNAWARE)];
+
+    while !stop.load(Ordering::Relaxed) {
+        // Eltávolított olvasók
+        states.retain(|rs| {
+            let gone = rs.name() != PNP_NOTIFICATION()
+                && rs.event_state().intersects(State::UNKNOWN | State::IGNORE);
+            if gone {
+                let reader = rs.name().to_string_lossy().to_string();
+                if rs.current_state().contains(State::PRESENT) {
+                    on_event(MonitorEvent::CardRemoved { reader: reader.clone() });
+                }
+                on_event(MonitorEvent::ReaderDetached { reader });
+            }
+            !gone
+        });
+
+        // Új olvasók
+        let names = match ctx.list_readers(&mut buffer) {
+            Ok(names) => names.map(|n| n.to_owned()).collect::<Vec<CString>>(),
+            Err(pcsc::Error::NoReadersAvailable) => Vec::new(),
+            Err(e) => return Err(e).context("Nem sikerült listázni az olvasókat"),
+        };
+        for name in names {
+            if !states.iter().any(|rs| rs.name() == name.as_c_str()) {
+                on_event(MonitorEvent::ReaderAttached {
+                    reader: name.to_string_lossy().to_string(),
+                });
+                states.push(ReaderState::new(name, State::UNAWARE));
+            }
+        }
+
+        for rs in &mut states {
+            rs.sync_current_state();
+        }
+        match ctx.get_status_change(POLL_TIMEOUT, &mut states) {
+            Ok(()) => {}
+            Err(pcsc::Error::Timeout) => continue,
+            Err(e) => return Err(e).context("Olvasó állapot lekérdezési hiba"),
+        }
+
+        for rs in &states {
+            if rs.name() == PNP_NOTIFICATION() || !rs.event_state().contains(State::CHANGED) {
+                continue;
+            }
+            let was_present = rs.current_state().contains(State::PRESENT);
+            let is_present = rs.event_state().contains(State::PRESENT);
+            let reader = rs.name().to_string_lossy().to_string();
+
+            if is_present && !was_present {
+                let (uid, chip) = identify(&ctx, rs.name());
+                on_event(MonitorEvent::CardInserted {
+                    reader,
+                    atr: to_hex(rs.atr()),
+                    uid,
+                    chip,
+                });
+            } else if was_present && !is_present {
+                on_event(MonitorEvent::CardRemoved { reader });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// UID és chip típus kiolvasása a frissen felhelyezett címkéről (hiba esetén None)
+fn identify(ctx: &PcscContext, reader: &std::ffi::CStr) -> (Option<String>, Option<NtagChip>) {
+    let card = match ctx.connect(reader, ShareMode::Shared, Protocols::ANY) {
+        Ok(card) => card,
+        Err(e) => {
+            warn!("Nem sikerült csatlakozni a címkéhez: {}", e);
+            return (None, None);
+        }
+    };
+
+    let ntag = Ntag216;
+    let uid = ntag.read_uid(&card).ok().map(|uid| to_hex(&uid));
+    let chip = ntag.detect_chip(&card).ok();
+
+    // Az eldobott pcsc::Card alaphelyzetbe állítaná (ResetCard) a címkét a munkamenet
+    // közös kapcsolata alatt, ezért érintetlenül hagyva bontjuk
+    if let Err((_, e)) = card.disconnect(Disposition::LeaveCard) {
+        warn!("Nem sikerült bontani a kapcsolatot: {}", e);
+    }
+    (uid, chip)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect()
+}
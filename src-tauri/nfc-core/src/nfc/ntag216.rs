@@ -0,0 +1,1349 @@
+use serde::{Deserialize, Serialize};
+use tracing::{debug, trace, warn};
+
+use super::card_trait::NfcCard;
+use super::error::{NfcError, Result};
+use super::driver::{NativeResponse, ReaderDriver};
+use super::lock::{self, LockPlan, LockRequest};
+use super::status_word;
+use super::transport::{self, RetryPolicy, TransmitErrorKind};
+use super::secret::Secret;
+use super::verify::{VerifyReport, WrittenPages};
+
+/// NTAG216 címke kezelése
+/// NTAG216 specifikáció:
+/// - Kapacitás: 888 bytes felhasználói adat
+/// - 135 blocks (4 bytes/block)
+/// - Block 0-3: UID és manufacturer data
+/// - Block 4-225 (E1h): User data (NTAG213: 4-39, NTAG215: 4-129, chipenként `NtagChip::user_end`)
+/// - Konfigurációs lapok a felhasználói memória után: dinamikus lock, CFG0, CFG1, PWD, PACK
+///   (NTAG216: E2h-E6h), a lap címek chipenként az `NtagChip`-ben
+pub struct Ntag216;
+
+/// Egy FAST_READ parancsban olvasott lapok száma (64 byte: minden olvasó puffere elbírja)
+const FAST_READ_PAGES: u8 = 16;
+
+/// CFG1 ACCESS byte bitjei
+const ACCESS_PROT: u8 = 0x80;
+const ACCESS_CFGLCK: u8 = 0x40;
+const ACCESS_AUTHLIM: u8 = 0x07;
+/// AUTH0: a password védelem a 4. laptól (a teljes felhasználói memória)
+const AUTH0_USER_MEMORY: u8 = 0x04;
+/// AUTH0 gyári értéke: nincs password védelem
+const AUTH0_DISABLED: u8 = 0xFF;
+
+impl Ntag216 {
+    /// APDU parancs küldése a címkének (átmeneti hibánál újrapróbálással)
+    fn transmit(&self, card: &dyn NfcCard, apdu: &[u8]) -> Result<Vec<u8>> {
+        // A válasz status byte-ok nélkül (SW != 9000 esetén osztályozott hiba)
+        card.transmit(apdu)
+    }
+
+    /// UID olvasása (PC/SC GET DATA: FF CA 00 00 00)
+    /// Password védelem mellett is olvasható, ezért használható a kulcs-diverzifikációhoz
+    pub fn read_uid(&self, card: &dyn NfcCard) -> Result<Vec<u8>> {
+        let uid = self.transmit(card, &[0xFF, 0xCA, 0x00, 0x00, 0x00])?;
+        if uid.len() != 7 {
+            return Err(NfcError::invalid_response(format!(
+                "Érvénytelen UID hossz: {} byte (NTAG216: 7 byte)",
+                uid.len()
+            )));
+        }
+        Ok(uid)
+    }
+
+    /// Block olvasása (4 bytes) - password opcionális
+    pub fn read_block(&self, card: &dyn NfcCard, block: u8) -> Result<[u8; 4]> {
+        self.read_block_with_password(card, block, None)
+    }
+
+    /// Block olvasása password-dal (ha szükséges)
+    pub fn read_block_with_password(&self, card: &dyn NfcCard, block: u8, password: Option<&[u8; 4]>) -> Result<[u8; 4]> {
+        // Ha password van megadva, először authenticate-olunk
+        if let Some(pwd) = password {
+            self.authenticate_password(card, pwd, None)?.ensure_ok()?;
+        }
+        
+        // READ command: CLA=0xFF, INS=0xB0, P1=block, P2=0x00, Le=0x04
+        let apdu = &[0xFF, 0xB0, 0x00, block, 0x04];
+        let response = self.transmit(card, apdu)?;
+        
+        if response.len() != 4 {
+            return Err(NfcError::invalid_response("Érvénytelen block méret"));
+        }
+
+        let mut block_data = [0u8; 4];
+        block_data.copy_from_slice(&response[..4]);
+        Ok(block_data)
+    }
+
+    /// Password authentication
+    /// NTAG216 PWD_AUTH parancs: 0x1B + 4 byte password, a címke a 2 byte-os PACK-kal válaszol
+    ///
+    /// Pontosan egy parancsot küld, az olvasónak megfelelő keretezésben. Minden sikertelen
+    /// PWD_AUTH csökkenti az AUTHLIM számlálót, ezért itt nincs próbálgatás.
+    /// Ha `expected_pack` meg van adva, a visszakapott PACK-ot összehasonlítjuk vele.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub fn authenticate_password(&self, card: &dyn NfcCard, password: &[u8; 4], expected_pack: Option<&[u8; 2]>) -> Result<AuthOutcome> {
+        let driver = ReaderDriver::identify(card);
+        debug!("PWD_AUTH ({}, {:?})", driver.label(), driver.framing());
+
+        // Nincs újrapróbálás: ha a címke megkapta a parancsot, de a válasz elveszett,
+        // egy ismételt hibás próbálkozás is az AUTHLIM-ből fogyna
+        let mut command = vec![0x1B];
+        command.extend_from_slice(password);
+        let response = match driver.transmit_native(card, &command, &RetryPolicy::NONE) {
+            Ok(response) => response,
+            // Az olvasó nem ismeri a keretezést (pl. nincs transparent session támogatás)
+            Err(NfcError::StatusWord { sw1, sw2, .. }) if is_unsupported_command(sw1, sw2) => {
+                warn!("Az olvasó nem támogatja a natív PWD_AUTH parancsot");
+                return Ok(AuthOutcome::Unsupported);
+            }
+            Err(e) => return Err(e),
+        };
+
+        let received = match response {
+            NativeResponse::Data(data) if data.len() >= 2 => [data[0], data[1]],
+            other => {
+                match other {
+                    NativeResponse::Nak(nak) => warn!("{}", status_word::describe_type2_ack(nak)),
+                    NativeResponse::NoResponse { reason } => warn!("{}", reason),
+                    NativeResponse::Data(data) => warn!("Rövid válasz: {:02X?}", data),
+                }
+                warn!("A címke elutasította a password-t");
+                return Ok(AuthOutcome::WrongPassword);
+            }
+        };
+        debug!("PACK: {:02X?}", received);
+
+        if let Some(expected) = expected_pack {
+            if received != *expected {
+                warn!("PACK eltérés: várt {:02X?}, kapott {:02X?}", expected, received);
+                return Ok(AuthOutcome::PackMismatch { expected: *expected, received });
+            }
+        }
+
+        debug!("Password authentication sikeres");
+        Ok(AuthOutcome::Ok { pack: received })
+    }
+
+    /// Natív NTAG parancs az olvasó driverén keresztül; a címke nyers válaszát adja vissza
+    pub fn transmit_native(&self, card: &dyn NfcCard, command: &[u8]) -> Result<Vec<u8>> {
+        ReaderDriver::identify(card)
+            .transmit_native(card, command, &transport::retry_policy())?
+            .into_data()
+    }
+
+    /// GET_VERSION (0x60): gyártó, termék típus és memória méret
+    pub fn get_version(&self, card: &dyn NfcCard) -> Result<NtagVersion> {
+        let data = self.transmit_native(card, &[0x60])?;
+        if data.len() != 8 {
+            return Err(NfcError::invalid_response(format!("Érvénytelen GET_VERSION válasz: {:02X?}", data)));
+        }
+        Ok(NtagVersion {
+            vendor_id: data[1],
+            product_type: data[2],
+            product_subtype: data[3],
+            major_version: data[4],
+            minor_version: data[5],
+            storage_size: data[6],
+            protocol_type: data[7],
+            chip: NtagChip::from_storage_size(data[6]),
+        })
+    }
+
+    /// READ_SIG (0x3C 00): az NXP által a UID-ra készített 32 byte-os ECC aláírás
+    pub fn read_signature(&self, card: &dyn NfcCard) -> Result<[u8; 32]> {
+        let data = self.transmit_native(card, &[0x3C, 0x00])?;
+        data.get(..32)
+            .and_then(|sig| <[u8; 32]>::try_from(sig).ok())
+            .ok_or_else(|| NfcError::invalid_response(format!("Érvénytelen READ_SIG válasz ({} byte)", data.len())))
+    }
+
+    /// FAST_READ (0x3A): a `start`..=`end` lapok, darabokban, hogy az olvasó puffere elég legyen
+    pub fn fast_read(&self, card: &dyn NfcCard, start: u8, end: u8) -> Result<Vec<u8>> {
+        if start > end {
+            return Err(NfcError::invalid_argument(format!("Érvénytelen lap tartomány: {}-{}", start, end)));
+        }
+        let mut result = Vec::with_capacity((end - start + 1) as usize * 4);
+        let mut page = start;
+        loop {
+            let last = end.min(page.saturating_add(FAST_READ_PAGES - 1));
+            let data = self.transmit_native(card, &[0x3A, page, last])?;
+            let expected = (last - page + 1) as usize * 4;
+            if data.len() < expected {
+                return Err(NfcError::invalid_response(format!(
+                    "Rövid FAST_READ válasz: {} byte ({} helyett)",
+                    data.len(),
+                    expected
+                )));
+            }
+            result.extend_from_slice(&data[..expected]);
+            if last == end {
+                return Ok(result);
+            }
+            page = last + 1;
+        }
+    }
+
+    /// READ_CNT (0x39 02): az NFC számláló (24 bit). None, ha a számláló nincs engedélyezve
+    /// (NFC_CNT_EN = 0), ilyenkor a címke NAK-kal válaszol és HALT állapotba kerül: a következő
+    /// parancs előtt a hívónak újra ki kell választania (`NfcSession::reconnect`).
+    pub fn read_counter(&self, card: &dyn NfcCard) -> Result<Option<u32>> {
+        match self.transmit_native(card, &[0x39, 0x02]) {
+            Ok(data) if data.len() >= 3 => Ok(Some(u32::from_le_bytes([data[0], data[1], data[2], 0]))),
+            Ok(data) => Err(NfcError::invalid_response(format!("Érvénytelen READ_CNT válasz: {:02X?}", data))),
+            Err(NfcError::TagNak { .. }) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Block írása (4 bytes) - password opcionális
+    pub fn write_block(&self, card: &dyn NfcCard, block: u8, data: &[u8; 4]) -> Result<()> {
+        self.write_block_with_password(card, block, data, None)
+    }
+
+    /// Block írása password-dal (ha szükséges)
+    /// NOTE: Az authentication-t már előzőleg meg kell tenni! Ez a függvény nem authenticate-ol.
+    pub fn write_block_with_password(&self, card: &dyn NfcCard, block: u8, data: &[u8; 4], password: Option<&[u8; 4]>) -> Result<()> {
+        // WRITE command: CLA=0xFF, INS=0xD6, P1=0x00, P2=block, Lc=0x04, data
+        if is_password_page(block) {
+            debug!("Block {} írása: {:?}", block, Secret(data));
+        } else {
+            debug!("Block {} írása: {:02X?}", block, data);
+        }
+        let mut apdu = vec![0xFF, 0xD6, 0x00, block, 0x04];
+        apdu.extend_from_slice(data);
+        
+        // Próbáljuk meg az írást (átmeneti RF hibánál újrapróbálással)
+        let response = transport::transmit_with_retry(card, &apdu, &transport::retry_policy())?;
+        let (sw1, sw2) = (response.sw1, response.sw2);
+        
+        if sw1 == 0x90 && sw2 == 0x00 {
+            debug!("Block {} sikeresen írva", block);
+            Ok(())
+        } else if transport::classify_status(sw1, sw2) == TransmitErrorKind::AuthFailure && password.is_none() {
+            // NAK / authentication hiba password nélkül - valószínűleg password védelem aktív
+            Err(NfcError::AuthRequired)
+        } else {
+            Err(NfcError::status_word(sw1, sw2))
+        }
+    }
+
+    /// NTAG216 típus ellenőrzése - password opcionális
+    pub fn check_type(&self, card: &dyn NfcCard) -> Result<bool> {
+        self.check_type_with_password(card, None)
+    }
+
+    /// NTAG216 típus ellenőrzése password-dal (ha szükséges)
+    pub fn check_type_with_password(&self, card: &dyn NfcCard, password: Option<&[u8; 4]>) -> Result<bool> {
+        // Próbáljuk meg olvasni Block 3-at password nélkül
+        match self.read_block_with_password(card, 3, None) {
+            Ok(cc) => {
+                // NTAG216 capability container: [E1 10 12 00]
+                // E1 = NDEF magic number
+                Ok(cc[0] == 0xE1)
+            }
+            // Password védelem aktív
+            Err(e) if e.is_auth_failure() => {
+                if let Some(pwd) = password {
+                    debug!("Password védelem aktív, authenticate-olunk...");
+                    let cc = self.read_block_with_password(card, 3, Some(pwd))?;
+                    Ok(cc[0] == 0xE1)
+                } else {
+                    warn!("Password védelem aktív, de nincs password megadva!");
+                    Err(NfcError::AuthRequired)
+                }
+            }
+            // Más hiba
+            Err(e) => Err(e),
+        }
+    }
+
+    /// NDEF üzenet olvasása
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub fn read_ndef(&self, card: &dyn NfcCard) -> Result<Option<String>> {
+        // Olvassuk a capability container-t
+        debug!("Block 3 olvasása (CC)...");
+        let cc = self.read_block(card, 3)
+            .map_err(|e| {
+                warn!("Block 3 olvasási hiba: {}", e);
+                e
+            })?;
+        debug!("CC: {:02X?}", cc);
+        
+        if cc[0] != 0xE1 {
+            warn!("Nincs NDEF (CC[0] = 0x{:02X}, nem 0xE1)", cc[0]);
+            return Ok(None); // Nincs NDEF üzenet
+        }
+        debug!("NDEF magic number megerősítve");
+        let chip = NtagChip::from_cc(&cc).ok_or(NfcError::UnknownChip { cc })?;
+
+        // Olvassuk az NDEF TLV-t (block 4)
+        debug!("Block 4 olvasása (TLV)...");
+        let tlv = self.read_block(card, 4)
+            .map_err(|e| {
+                warn!("Block 4 olvasási hiba: {}", e);
+                e
+            })?;
+        debug!("TLV: {:02X?}", tlv);
+        
+        // TLV formátum: [Tag] [Length] [Value...], 255 byte-tól [Tag] [FF] [Length hi] [Length lo]
+        let Some((length, header_len)) = parse_ndef_tlv_header(&tlv) else {
+            warn!("Nem NDEF TLV (Tag = 0x{:02X}, nem 0x03)", tlv[0]);
+            return Ok(None); // Nem NDEF TLV
+        };
+        debug!("NDEF hossz: {} bytes", length);
+        if length == 0 {
+            warn!("Üres NDEF üzenet");
+            return Ok(None);
+        }
+
+        // Olvassuk az NDEF üzenetet
+        debug!("NDEF adatok olvasása...");
+        let mut ndef_data = Vec::new();
+        let mut block = 4 + (header_len / 4) as u8;
+        let mut offset = header_len % 4; // TLV header után
+        
+        while ndef_data.len() < length {
+            let block_data = self.read_block(card, block)
+                .map_err(|e| {
+                    warn!("Block {} olvasási hiba: {}", block, e);
+                    e
+                })?;
+            
+            for i in offset..4 {
+                if ndef_data.len() < length {
+                    ndef_data.push(block_data[i]);
+                }
+            }
+            
+            block += 1;
+            offset = 0;
+            
+            if block > chip.user_end() {
+                warn!("Elértük a felhasználói memória végét, leállítás");
+                break;
+            }
+        }
+        
+        debug!("Olvasott NDEF adatok ({} bytes): {:02X?}", ndef_data.len(), ndef_data);
+
+        // Parse NDEF üzenet
+        debug!("NDEF parse-olás...");
+        let result = self.parse_ndef_url(&ndef_data);
+        match &result {
+            Ok(Some(url)) => debug!("URL parse-olva: {}", url),
+            Ok(None) => warn!("Nem sikerült parse-olni az URL-t"),
+            Err(e) => warn!("Parse hiba: {}", e),
+        }
+        result
+    }
+
+    /// NDEF URL üzenet írása
+    pub fn write_ndef_url(&self, card: &dyn NfcCard, url: &str) -> Result<WrittenPages> {
+        self.write_ndef_url_with_password(card, url, None, None)
+    }
+
+    pub fn write_ndef_url_with_password(&self, card: &dyn NfcCard, url: &str, password: Option<&[u8; 4]>, pack: Option<&[u8; 2]>) -> Result<WrittenPages> {
+        if let Some(_pwd) = password {
+            debug!("Password védett írás");
+        }
+        
+        // Ellenőrizzük, hogy NTAG216-e
+        if !self.check_type(card)? {
+            return Err(NfcError::NotNtag);
+        }
+
+        // Készítsük el az NDEF URL üzenetet
+        let ndef_message = self.create_ndef_url(url)?;
+        let written = self.write_ndef_message_with_password(card, &ndef_message, password, pack)?;
+        
+        debug!("NDEF URL sikeresen írva");
+        Ok(written)
+    }
+
+    /// NDEF URL üzenet létrehozása
+    fn create_ndef_url(&self, url: &str) -> Result<Vec<u8>> {
+        // NDEF Record formátum:
+        // [Header] [Type Length] [Payload Length] [Type] [Payload]
+        
+        let url_bytes = url.as_bytes();
+        let url_len = url_bytes.len();
+        
+        if url_len > 250 {
+            return Err(NfcError::OutOfCapacity { size: url_len, max: 250 });
+        }
+
+        // Payload: [URI Prefix Code] [URI...]
+        // NDEF URI prefix codes:
+        // 0x01 = http://www.
+        // 0x02 = https://www.
+        // 0x03 = http://
+        // 0x04 = https://
+        let (prefix_code, url_without_prefix) = if url.starts_with("http://www.") {
+            (0x01, &url[11..]) // "http://www." után
+        } else if url.starts_with("https://www.") {
+            (0x02, &url[12..]) // "https://www." után
+        } else if url.starts_with("http://") {
+            (0x03, &url[7..]) // "http://" után
+        } else if url.starts_with("https://") {
+            (0x04, &url[8..]) // "https://" után
+        } else {
+            (0x00, url) // Nincs prefix
+        };
+        
+        // Header byte:
+        // MB=1 (Message Begin), ME=1 (Message End), CF=0, SR=1 (Short Record), IL=0, TNF=0x01 (Well Known)
+        let header = 0xD1; // 1101 0001
+        
+        // Type Length: 1 (U = 0x55)
+        let type_length = 0x01;
+        
+        // Payload Length (short record, 1 byte)
+        // Prefix code (1 byte) + URL hossza
+        let payload_length = (1 + url_without_prefix.len()) as u8;
+        
+        // Type: U (0x55) = URI Record
+        let type_byte = 0x55;
+        
+        let mut payload = vec![prefix_code];
+        
+        payload.extend_from_slice(url_without_prefix.as_bytes());
+        
+        // Összeállítjuk az NDEF üzenetet
+        let mut ndef = vec![header, type_length, payload_length, type_byte];
+        ndef.extend_from_slice(&payload);
+        
+        Ok(ndef)
+    }
+
+    /// NDEF üzenet parse-olása URL-lé
+    fn parse_ndef_url(&self, ndef_data: &[u8]) -> Result<Option<String>> {
+        debug!("parse_ndef_url() részletes elemzés:");
+        trace!("NDEF adatok hossza: {} bytes", ndef_data.len());
+        trace!("NDEF adatok: {:02X?}", ndef_data);
+        
+        if ndef_data.is_empty() {
+            warn!("Üres NDEF adatok");
+            return Ok(None);
+        }
+
+        // Olvassuk a header byte-ot
+        let header = ndef_data[0];
+        trace!("Header: 0x{:02X}", header);
+        
+        // Ellenőrizzük, hogy Well Known Type-e
+        let tnf = header & 0x07;
+        trace!("TNF: {}", tnf);
+        if tnf != 0x01 {
+            warn!("Nem Well Known Type (TNF={})", tnf);
+            return Ok(None);
+        }
+
+        // Type Length
+        if ndef_data.len() < 3 {
+            warn!("NDEF adatok túl rövidek (<3 bytes)");
+            return Ok(None);
+        }
+        let type_length = ndef_data[1] as usize;
+        trace!("Type Length: {}", type_length);
+        
+        // Payload Length (short record)
+        let payload_length = ndef_data[2] as usize;
+        trace!("Payload Length: {} bytes", payload_length);
+        
+        // Type byte
+        if ndef_data.len() < 4 + type_length {
+            warn!("NDEF adatok túl rövidek (<4+type_length bytes)");
+            return Ok(None);
+        }
+        let type_byte = ndef_data[3];
+        trace!("Type byte: 0x{:02X}", type_byte);
+        
+        if type_byte != 0x55 {
+            warn!("Nem URI record (Type=0x{:02X}, nem 0x55)", type_byte);
+            return Ok(None); // Nem URI record
+        }
+
+        // Payload pozíció: Header(1) + TypeLength(1) + PayloadLength(1) + Type(type_length)
+        let payload_start = 3 + type_length;
+        trace!("Payload start pozíció: {}", payload_start);
+        
+        if ndef_data.len() < payload_start + payload_length {
+            warn!("NDEF adatok túl rövidek (len={}, szükséges={})", 
+                ndef_data.len(), payload_start + payload_length);
+            return Ok(None);
+        }
+        
+        let payload = &ndef_data[payload_start..payload_start + payload_length];
+        trace!("Payload ({} bytes): {:02X?}", payload.len(), payload);
+        
+        if payload.is_empty() {
+            warn!("Üres payload");
+            return Ok(None);
+        }
+
+        // Prefix code
+        let prefix_code = payload[0];
+        let url_part = &payload[1..];
+        trace!("Prefix code: 0x{:02X}", prefix_code);
+        trace!("URL rész: {:02X?} = \"{}\"", url_part, String::from_utf8_lossy(url_part));
+        
+        let url = match prefix_code {
+            0x01 => format!("http://www.{}", String::from_utf8_lossy(url_part)),
+            0x02 => format!("https://www.{}", String::from_utf8_lossy(url_part)),
+            0x03 => format!("http://{}", String::from_utf8_lossy(url_part)),
+            0x04 => format!("https://{}", String::from_utf8_lossy(url_part)),
+            _ => {
+                warn!("Ismeretlen prefix code: 0x{:02X}, teljes payload-t használjuk", prefix_code);
+                String::from_utf8_lossy(payload).to_string()
+            },
+        };
+
+        debug!("Parse-olt URL: {}", url);
+        Ok(Some(url))
+    }
+
+    /// Password védelem beállítása a chip konfigurációs lapjain
+    ///
+    /// Sorrend: PWD, PACK, CFG1 (AUTHLIM; PROT=0, azaz csak az írás védett, az NDEF üzenet
+    /// password nélkül is olvasható), végül a CFG0 AUTH0 byte-ja. Az AUTH0 kapcsolja be a
+    /// védelmet, ezért kerül utoljára: egy félbeszakadt beállítás nem hagy hátra ismeretlen
+    /// password-del védett címkét.
+    ///
+    /// `auth_limit`: AUTHLIM (0: nincs korlát, 1-7: legfeljebb 2^n hibás próbálkozás)
+    #[tracing::instrument(level = "debug", skip_all, fields(auth_limit))]
+    pub fn set_password(&self, card: &dyn NfcCard, password: &[u8; 4], pack: &[u8; 2], auth_limit: u8) -> Result<()> {
+        trace!(password = ?Secret(password), pack = ?pack, "Új password");
+        if auth_limit > ACCESS_AUTHLIM {
+            return Err(NfcError::invalid_argument(format!(
+                "Az auth limit 0-7 lehet (2^n próbálkozás), kapott: {}",
+                auth_limit
+            )));
+        }
+
+        let chip = self.detect_chip(card)?;
+        let cfg0 = self.read_block(card, chip.cfg0_page())?;
+        let cfg1 = self.read_block(card, chip.cfg1_page())?;
+        debug!(?chip, cfg0 = ?cfg0, cfg1 = ?cfg1, "Jelenlegi konfiguráció");
+        if cfg1[0] & ACCESS_CFGLCK != 0 {
+            return Err(NfcError::TagLocked);
+        }
+
+        self.write_block(card, chip.pwd_page(), password)?;
+        self.write_block(card, chip.pack_page(), &[pack[0], pack[1], 0x00, 0x00])?;
+
+        let access = (cfg1[0] & !(ACCESS_PROT | ACCESS_AUTHLIM)) | auth_limit;
+        self.write_block(card, chip.cfg1_page(), &[access, cfg1[1], cfg1[2], cfg1[3]])?;
+
+        self.write_block(card, chip.cfg0_page(), &[cfg0[0], cfg0[1], cfg0[2], AUTH0_USER_MEMORY])?;
+        debug!("Password védelem bekapcsolva a(z) {}. laptól", AUTH0_USER_MEMORY);
+        Ok(())
+    }
+
+    /// Password védelem eltávolítása: AUTH0 kikapcsolása, majd a gyári PWD (FFFFFFFF),
+    /// PACK (0000) és AUTHLIM visszaállítása
+    ///
+    /// Védett címkén a konfigurációs lapok csak sikeres PWD_AUTH után írhatók, ezért az első
+    /// írás előtt a jelenlegi password-del authenticate-olunk.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub fn remove_password(&self, card: &dyn NfcCard, password: &[u8; 4], pack: Option<&[u8; 2]>) -> Result<()> {
+        trace!(password = ?Secret(password), pack = ?pack, "Jelenlegi password");
+        self.authenticate_password(card, password, pack)?.ensure_ok()?;
+
+        let chip = self.detect_chip(card)?;
+        let cfg0 = self.read_block(card, chip.cfg0_page())?;
+        let cfg1 = self.read_block(card, chip.cfg1_page())?;
+        if cfg1[0] & ACCESS_CFGLCK != 0 {
+            return Err(NfcError::TagLocked);
+        }
+
+        self.write_block(card, chip.cfg0_page(), &[cfg0[0], cfg0[1], cfg0[2], AUTH0_DISABLED])?;
+        let access = cfg1[0] & !(ACCESS_PROT | ACCESS_AUTHLIM);
+        self.write_block(card, chip.cfg1_page(), &[access, cfg1[1], cfg1[2], cfg1[3]])?;
+        self.write_block(card, chip.pwd_page(), &[0xFF; 4])?;
+        self.write_block(card, chip.pack_page(), &[0x00; 4])?;
+        Ok(())
+    }
+
+    /// Read-only mód beállítása (VISSZAFORDÍTHATATLAN!)
+    /// Minden felhasználói lapot zárol a statikus és dinamikus lock bitekkel, beállítja a
+    /// CFGLCK-t és a CC access byte-ot 0x0F-ra
+    pub fn set_read_only(&self, card: &dyn NfcCard) -> Result<()> {
+        let chip = self.detect_chip(card)?;
+        let plan = lock::plan_lock(chip, &LockRequest::read_only(chip))?;
+        self.apply_lock(card, &plan)
+    }
+
+    /// Chip típus felismerése a Capability Container alapján
+    pub fn detect_chip(&self, card: &dyn NfcCard) -> Result<NtagChip> {
+        let cc = self.read_block(card, 3)?;
+        NtagChip::from_cc(&cc)
+            .ok_or(NfcError::UnknownChip { cc })
+    }
+
+    /// Zárolási terv (dry-run): megmutatja, mely lapok válnak véglegesen read-only-vá.
+    /// Nem ír a címkére.
+    pub fn plan_lock(&self, card: &dyn NfcCard, request: &LockRequest) -> Result<LockPlan> {
+        let chip = self.detect_chip(card)?;
+        lock::plan_lock(chip, request)
+    }
+
+    /// Zárolási terv végrehajtása (VISSZAFORDÍTHATATLAN!)
+    /// Sorrend: CC, CFGLCK, dinamikus lock byte-ok, végül a statikus lock byte-ok,
+    /// mert az L-CC bit után a CC már nem írható.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub fn apply_lock(&self, card: &dyn NfcCard, plan: &LockPlan) -> Result<()> {
+        if plan.is_empty() {
+            return Err(NfcError::invalid_argument("A zárolási terv üres, nincs mit írni"));
+        }
+
+        let chip = self.detect_chip(card)?;
+        if chip != plan.chip {
+            return Err(NfcError::ChipMismatch { expected: plan.chip, actual: chip });
+        }
+
+        if plan.cc_read_only {
+            // A CC OTP: a bitek csak 1-be állíthatók, ezért a meglévő értékekkel írjuk
+            let cc = self.read_block(card, 3)?;
+            debug!("CC access byte = 0x0F");
+            self.write_block(card, 3, &[cc[0], cc[1], cc[2], 0x0F])?;
+        }
+
+        if plan.cfglck {
+            let cfg1_page = chip.cfg1_page();
+            let cfg1 = self.read_block(card, cfg1_page)?;
+            debug!("CFGLCK (Block {})", cfg1_page);
+            self.write_block(card, cfg1_page, &[cfg1[0] | 0x40, cfg1[1], cfg1[2], cfg1[3]])?;
+        }
+
+        if plan.dynamic_lock != [0, 0, 0] {
+            let dynamic_page = chip.dynamic_lock_page();
+            let d = plan.dynamic_lock;
+            debug!("Dinamikus lock byte-ok (Block {}): {:02X?}", dynamic_page, d);
+            self.write_block(card, dynamic_page, &[d[0], d[1], d[2], 0x00])?;
+        }
+
+        if plan.static_lock != [0, 0] {
+            // A 2. lap első két byte-ját (BCC1, belső) a címke írásnál figyelmen kívül hagyja
+            let l = plan.static_lock;
+            debug!("Statikus lock byte-ok (Block 2): {:02X?}", l);
+            self.write_block(card, 2, &[0x00, 0x00, l[0], l[1]])?;
+        }
+
+        debug!("Zárolás kész: {} lap read-only", plan.locked_pages.len());
+        Ok(())
+    }
+
+    /// Konfiguráció olvasása (CFG0, CFG1). A PWD és a PACK nem olvasható vissza.
+    pub fn read_config(&self, card: &dyn NfcCard) -> Result<NtagConfig> {
+        let chip = self.detect_chip(card)?;
+        let cfg0 = self.read_block(card, chip.cfg0_page())?;
+        let cfg1 = self.read_block(card, chip.cfg1_page())?;
+        let auth0 = cfg0[3];
+        let access = cfg1[0];
+
+        Ok(NtagConfig {
+            chip,
+            auth0,
+            access,
+            auth_limit: access & ACCESS_AUTHLIM,
+            password_protected: auth0 <= chip.pack_page(),
+            read_protected: access & ACCESS_PROT != 0,
+            config_locked: access & ACCESS_CFGLCK != 0,
+        })
+    }
+
+    /// NDEF Text Record írása
+    pub fn write_ndef_text(&self, card: &dyn NfcCard, text: &str, language: &str) -> Result<WrittenPages> {
+        self.write_ndef_text_with_password(card, text, language, None, None)
+    }
+
+    pub fn write_ndef_text_with_password(&self, card: &dyn NfcCard, text: &str, language: &str, password: Option<&[u8; 4]>, pack: Option<&[u8; 2]>) -> Result<WrittenPages> {
+        if !self.check_type(card)? {
+            return Err(NfcError::NotNtag);
+        }
+
+        let ndef_message = self.create_ndef_text(text, language)?;
+        self.write_ndef_message_with_password(card, &ndef_message, password, pack)
+    }
+
+    /// NDEF Text Record olvasása
+    pub fn read_ndef_text(&self, card: &dyn NfcCard) -> Result<Option<(String, String)>> {
+        let ndef_data = self.read_ndef_raw(card)?;
+        if let Some(data) = ndef_data {
+            self.parse_ndef_text(&data)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// NDEF vCard írása
+    pub fn write_ndef_vcard(&self, card: &dyn NfcCard, vcard: &str) -> Result<WrittenPages> {
+        self.write_ndef_vcard_with_password(card, vcard, None, None)
+    }
+
+    pub fn write_ndef_vcard_with_password(&self, card: &dyn NfcCard, vcard: &str, password: Option<&[u8; 4]>, pack: Option<&[u8; 2]>) -> Result<WrittenPages> {
+        if !self.check_type(card)? {
+            return Err(NfcError::NotNtag);
+        }
+
+        let ndef_message = self.create_ndef_vcard(vcard)?;
+        self.write_ndef_message_with_password(card, &ndef_message, password, pack)
+    }
+
+    /// NDEF vCard olvasása
+    pub fn read_ndef_vcard(&self, card: &dyn NfcCard) -> Result<Option<String>> {
+        let ndef_data = self.read_ndef_raw(card)?;
+        if let Some(data) = ndef_data {
+            self.parse_ndef_vcard(&data)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// NDEF üzenet törlése
+    pub fn clear_ndef(&self, card: &dyn NfcCard) -> Result<WrittenPages> {
+        self.clear_ndef_with_password(card, None, None)
+    }
+
+    pub fn clear_ndef_with_password(&self, card: &dyn NfcCard, password: Option<&[u8; 4]>, pack: Option<&[u8; 2]>) -> Result<WrittenPages> {
+        // Ha password van, authenticate-olunk először
+        if let Some(pwd) = password {
+            self.authenticate_password(card, pwd, pack)?.ensure_ok()?;
+        }
+
+        // TLV terminátor írása (üres NDEF üzenet)
+        let terminator = [0xFE, 0x00, 0x00, 0x00];
+        self.write_block_with_password(card, 4, &terminator, password)?;
+        let mut written = WrittenPages::new(4);
+        written.push(&terminator);
+        
+        // További blokkok törlése (opcionális)
+        for block in 5..=10 {
+            let empty = [0x00, 0x00, 0x00, 0x00];
+            self.write_block_with_password(card, block, &empty, password)?;
+            written.push(&empty);
+        }
+        
+        Ok(written)
+    }
+
+    /// Raw byte írása (NDEF nélkül)
+    pub fn write_raw_bytes(&self, card: &dyn NfcCard, start_block: u8, data: &[u8]) -> Result<WrittenPages> {
+        self.write_raw_bytes_with_password(card, start_block, data, None, None)
+    }
+
+    pub fn write_raw_bytes_with_password(&self, card: &dyn NfcCard, start_block: u8, data: &[u8], password: Option<&[u8; 4]>, pack: Option<&[u8; 2]>) -> Result<WrittenPages> {
+        let chip = self.detect_chip(card)?;
+        check_user_page(chip, start_block)?;
+
+        // Ha password van, authenticate-olunk először
+        if let Some(pwd) = password {
+            self.authenticate_password(card, pwd, pack)?.ensure_ok()?;
+        }
+
+        let mut block = start_block;
+        let mut data_index = 0;
+        let mut written = WrittenPages::new(start_block);
+        
+        while data_index < data.len() && block <= chip.user_end() {
+            let mut block_data = [0u8; 4];
+            for i in 0..4 {
+                if data_index < data.len() {
+                    block_data[i] = data[data_index];
+                    data_index += 1;
+                } else {
+                    block_data[i] = 0x00;
+                }
+            }
+            self.write_block_with_password(card, block, &block_data, password)?;
+            written.push(&block_data);
+            block += 1;
+        }
+        
+        Ok(written)
+    }
+
+    /// Raw byte olvasása
+    pub fn read_raw_bytes(&self, card: &dyn NfcCard, start_block: u8, count: u8) -> Result<Vec<u8>> {
+        let chip = self.detect_chip(card)?;
+        check_user_page(chip, start_block)?;
+
+        // Egyetlen FAST_READ, ha az olvasó továbbítja; különben lapról lapra. Ha a címke
+        // NAK-ot adott, HALT állapotban van: nem olvasunk tovább, a hívó választja ki újra.
+        let end_block = (start_block as usize + (count as usize).div_ceil(4)).saturating_sub(1).min(chip.user_end() as usize) as u8;
+        if count > 0 {
+            match self.fast_read(card, start_block, end_block) {
+                Ok(mut data) => {
+                    data.truncate(count as usize);
+                    return Ok(data);
+                }
+                Err(e) if e.leaves_tag_halted() => return Err(e),
+                Err(e) => warn!("FAST_READ nem sikerült ({}), lapról lapra olvasunk", e),
+            }
+        }
+
+        self.read_user_pages(card, chip, start_block, count)
+    }
+
+    /// Raw byte olvasása lapról lapra (READ), FAST_READ nélkül
+    pub fn read_raw_bytes_by_page(&self, card: &dyn NfcCard, start_block: u8, count: u8) -> Result<Vec<u8>> {
+        let chip = self.detect_chip(card)?;
+        self.read_user_pages(card, chip, start_block, count)
+    }
+
+    /// Felhasználói lapok olvasása egyenként, legfeljebb a chip utolsó felhasználói lapjáig
+    fn read_user_pages(&self, card: &dyn NfcCard, chip: NtagChip, start_block: u8, count: u8) -> Result<Vec<u8>> {
+        check_user_page(chip, start_block)?;
+
+        let mut result = Vec::new();
+        let mut block = start_block;
+        let mut remaining = count as usize;
+        
+        while remaining > 0 && block <= chip.user_end() {
+            let block_data = self.read_block(card, block)?;
+            let to_take = remaining.min(4);
+            result.extend_from_slice(&block_data[..to_take]);
+            remaining -= to_take;
+            block += 1;
+        }
+        
+        Ok(result)
+    }
+
+    /// Írás ellenőrzése: a kiírt lapok visszaolvasása és byte-onkénti összehasonlítása
+    /// (a password authentication a kapcsolat végéig érvényes, így védett címkén is olvasható)
+    pub fn verify_written(&self, card: &dyn NfcCard, written: &WrittenPages) -> Result<VerifyReport> {
+        debug!("Visszaolvasás: {} lap a(z) {}. laptól", written.page_count(), written.start_page);
+        let mut read_back = Vec::with_capacity(written.page_count());
+        for (page, _) in written.pages() {
+            read_back.push(self.read_block(card, page)?);
+        }
+        
+        let report = VerifyReport::compare(written, &read_back);
+        if report.is_ok() {
+            debug!("Visszaolvasás egyezik");
+        } else {
+            warn!("Visszaolvasás eltér: {} lap", report.mismatches.len());
+        }
+        Ok(report)
+    }
+
+    // Helper függvények
+
+    fn write_ndef_message(&self, card: &dyn NfcCard, ndef_message: &[u8]) -> Result<WrittenPages> {
+        self.write_ndef_message_with_password(card, ndef_message, None, None)
+    }
+
+    fn write_ndef_message_with_password(&self, card: &dyn NfcCard, ndef_message: &[u8], password: Option<&[u8; 4]>, pack: Option<&[u8; 2]>) -> Result<WrittenPages> {
+        // Ha password van, authenticate-olunk először
+        if let Some(pwd) = password {
+            self.authenticate_password(card, pwd, pack)?.ensure_ok()?;
+        }
+
+        self.write_ndef_tlv(card, ndef_message, password)
+    }
+
+    /// NDEF TLV írása tearing-biztos sorrendben (NFC Forum Type 2 Tag ajánlás):
+    /// 1. a TLV hossz nullázása (a címke érvényes, üres NDEF üzenetet tartalmaz),
+    /// 2. az üzenet többi részének és a terminátor TLV-nek az írása,
+    /// 3. végül a valódi hossz beírása.
+    ///
+    /// Ha a címkét írás közben elveszik, üres de érvényes NDEF marad rajta, nem sérült adat.
+    #[tracing::instrument(level = "debug", skip_all)]
+    fn write_ndef_tlv(&self, card: &dyn NfcCard, ndef_message: &[u8], password: Option<&[u8; 4]>) -> Result<WrittenPages> {
+        let chip = self.detect_chip(card)?;
+        let pages = ndef_tlv_pages(chip, ndef_message)?;
+
+        debug!("NDEF üzenet írása ({} bytes, {} lap)...", ndef_message.len(), pages.len());
+
+        // 1. Hossz nullázása (3 byte-os formánál a hossz mindhárom byte-ja): 03 00 00 00 érvényes, üres NDEF
+        let mut empty_first_page = pages[0];
+        empty_first_page[1..].fill(0x00);
+        self.write_block_with_password(card, 4, &empty_first_page, password)?;
+
+        // 2. Tartalom és terminátor
+        for (offset, page) in pages.iter().enumerate().skip(1) {
+            self.write_block_with_password(card, 4 + offset as u8, page, password)?;
+        }
+
+        // 3. Valódi hossz utoljára
+        self.write_block_with_password(card, 4, &pages[0], password)?;
+
+        let mut written = WrittenPages::new(4);
+        for page in &pages {
+            written.push(page);
+        }
+        Ok(written)
+    }
+
+    fn read_ndef_raw(&self, card: &dyn NfcCard) -> Result<Option<Vec<u8>>> {
+        let cc = self.read_block(card, 3)?;
+        if cc[0] != 0xE1 {
+            return Ok(None);
+        }
+        let chip = NtagChip::from_cc(&cc).ok_or(NfcError::UnknownChip { cc })?;
+
+        let tlv = self.read_block(card, 4)?;
+        let Some((length, header_len)) = parse_ndef_tlv_header(&tlv) else {
+            return Ok(None);
+        };
+        if length == 0 {
+            return Ok(None);
+        }
+
+        let mut ndef_data = Vec::new();
+        let mut block = 4 + (header_len / 4) as u8;
+        let mut offset = header_len % 4;
+        
+        while ndef_data.len() < length && block <= chip.user_end() {
+            let block_data = self.read_block(card, block)?;
+            for i in offset..4 {
+                if ndef_data.len() < length {
+                    ndef_data.push(block_data[i]);
+                }
+            }
+            block += 1;
+            offset = 0;
+        }
+
+        Ok(Some(ndef_data))
+    }
+
+    fn create_ndef_text(&self, text: &str, language: &str) -> Result<Vec<u8>> {
+        let text_bytes = text.as_bytes();
+        let lang_bytes = language.as_bytes();
+        
+        if lang_bytes.len() > 5 {
+            return Err(NfcError::invalid_argument("A nyelv kód túl hosszú (max 5 karakter)"));
+        }
+        
+        if text_bytes.len() > 200 {
+            return Err(NfcError::OutOfCapacity { size: text_bytes.len(), max: 200 });
+        }
+
+        let header = 0xD1; // MB=1, ME=1, SR=1, TNF=001
+        let type_length = 0x01; // "T" = 0x54
+        let payload_length = (1 + lang_bytes.len() + text_bytes.len()) as u8;
+        let type_byte = 0x54; // "T" = Text Record
+        
+        let mut payload = vec![lang_bytes.len() as u8];
+        payload.extend_from_slice(lang_bytes);
+        payload.extend_from_slice(text_bytes);
+        
+        let mut ndef = vec![header, type_length, payload_length, type_byte];
+        ndef.extend_from_slice(&payload);
+        
+        Ok(ndef)
+    }
+
+    fn parse_ndef_text(&self, ndef_data: &[u8]) -> Result<Option<(String, String)>> {
+        if ndef_data.len() < 4 {
+            return Ok(None);
+        }
+
+        let header = ndef_data[0];
+        let tnf = header & 0x07;
+        if tnf != 0x01 {
+            return Ok(None);
+        }
+
+        let type_length = ndef_data[1] as usize;
+        let payload_length = ndef_data[2] as usize;
+        
+        if ndef_data.len() < 4 + type_length + payload_length {
+            return Ok(None);
+        }
+
+        let type_byte = ndef_data[3];
+        if type_byte != 0x54 {
+            return Ok(None);
+        }
+
+        let payload_start = 4 + type_length;
+        let payload = &ndef_data[payload_start..payload_start + payload_length];
+        
+        if payload.is_empty() {
+            return Ok(None);
+        }
+
+        let lang_length = payload[0] as usize;
+        if payload.len() < 1 + lang_length {
+            return Ok(None);
+        }
+
+        let language = String::from_utf8_lossy(&payload[1..1 + lang_length]).to_string();
+        let text = String::from_utf8_lossy(&payload[1 + lang_length..]).to_string();
+        
+        Ok(Some((text, language)))
+    }
+
+    fn create_ndef_vcard(&self, vcard: &str) -> Result<Vec<u8>> {
+        let vcard_bytes = vcard.as_bytes();
+        
+        if vcard_bytes.len() > 400 {
+            return Err(NfcError::OutOfCapacity { size: vcard_bytes.len(), max: 400 });
+        }
+
+        let header = 0xD2; // MB=1, ME=1, SR=1, TNF=010 (MIME)
+        let type_length = 0x0A; // "text/vcard" = 10 karakter
+        let payload_length = vcard_bytes.len() as u16;
+        
+        // Short record csak 1 byte payload length, ha >255 akkor long record
+        if payload_length > 255 {
+            return Err(NfcError::OutOfCapacity { size: payload_length as usize, max: 255 });
+        }
+        
+        let type_bytes = b"text/vcard";
+        let mut ndef = vec![header, type_length, payload_length as u8];
+        ndef.extend_from_slice(type_bytes);
+        ndef.extend_from_slice(vcard_bytes);
+        
+        Ok(ndef)
+    }
+
+    fn parse_ndef_vcard(&self, ndef_data: &[u8]) -> Result<Option<String>> {
+        if ndef_data.len() < 4 {
+            return Ok(None);
+        }
+
+        let header = ndef_data[0];
+        let tnf = header & 0x07;
+        if tnf != 0x02 {
+            return Ok(None); // Nem MIME type
+        }
+
+        let type_length = ndef_data[1] as usize;
+        let payload_length = ndef_data[2] as usize;
+        
+        if ndef_data.len() < 4 + type_length + payload_length {
+            return Ok(None);
+        }
+
+        let type_bytes = &ndef_data[3..3 + type_length];
+        if type_bytes != b"text/vcard" {
+            return Ok(None);
+        }
+
+        let payload_start = 3 + type_length;
+        let vcard = String::from_utf8_lossy(&ndef_data[payload_start..payload_start + payload_length]).to_string();
+        
+        Ok(Some(vcard))
+    }
+}
+
+/// NTAG21x felhasználói konfiguráció (CFG0 / CFG1)
+#[derive(Debug, Clone)]
+pub struct NtagConfig {
+    pub chip: NtagChip,
+    /// Az első password védett lap
+    pub auth0: u8,
+    /// CFG1 ACCESS byte (PROT, CFGLCK, NFC_CNT_EN, AUTHLIM)
+    pub access: u8,
+    /// AUTHLIM (0: nincs korlát, különben legfeljebb 2^n hibás próbálkozás)
+    pub auth_limit: u8,
+    /// Az AUTH0 a címke memóriáján belül van
+    pub password_protected: bool,
+    /// PROT: az olvasás is password-höz kötött
+    pub read_protected: bool,
+    /// CFGLCK: a konfiguráció véglegesen zárolt
+    pub config_locked: bool,
+}
+
+/// GET_VERSION válasz
+#[derive(Debug, Clone, Serialize)]
+pub struct NtagVersion {
+    /// 0x04 = NXP
+    pub vendor_id: u8,
+    /// 0x04 = NTAG
+    pub product_type: u8,
+    pub product_subtype: u8,
+    pub major_version: u8,
+    pub minor_version: u8,
+    /// 0x0F = NTAG213, 0x11 = NTAG215, 0x13 = NTAG216
+    pub storage_size: u8,
+    /// 0x03 = ISO/IEC 14443-3
+    pub protocol_type: u8,
+    pub chip: Option<NtagChip>,
+}
+
+/// NTAG21x chip típus
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NtagChip {
+    Ntag213,
+    Ntag215,
+    Ntag216,
+}
+
+impl NtagChip {
+    /// Felismerés a CC méret byte-ja alapján (12h, 3Eh, 6Dh)
+    pub fn from_cc(cc: &[u8; 4]) -> Option<Self> {
+        if cc[0] != 0xE1 {
+            return None;
+        }
+        match cc[2] {
+            0x12 => Some(NtagChip::Ntag213),
+            0x3E => Some(NtagChip::Ntag215),
+            0x6D => Some(NtagChip::Ntag216),
+            _ => None,
+        }
+    }
+
+    /// Felismerés a GET_VERSION memória méret byte-ja alapján
+    pub fn from_storage_size(storage_size: u8) -> Option<Self> {
+        match storage_size {
+            0x0F => Some(NtagChip::Ntag213),
+            0x11 => Some(NtagChip::Ntag215),
+            0x13 => Some(NtagChip::Ntag216),
+            _ => None,
+        }
+    }
+
+    /// Utolsó felhasználói lap
+    pub fn user_end(self) -> u8 {
+        match self {
+            NtagChip::Ntag213 => 0x27,
+            NtagChip::Ntag215 => 0x81,
+            NtagChip::Ntag216 => 0xE1,
+        }
+    }
+
+    /// PWD lap (a password, csak írható)
+    pub fn pwd_page(self) -> u8 {
+        self.user_end() + 4
+    }
+
+    /// PACK lap (2 byte PACK + 2 RFUI byte, nem olvasható vissza)
+    pub fn pack_page(self) -> u8 {
+        self.user_end() + 5
+    }
+
+    /// Dinamikus lock byte-ok lapja (közvetlenül a felhasználói memória után)
+    pub fn dynamic_lock_page(self) -> u8 {
+        self.user_end() + 1
+    }
+
+    /// CFG0 lap (MIRROR, AUTH0)
+    pub fn cfg0_page(self) -> u8 {
+        self.user_end() + 2
+    }
+
+    /// CFG1 lap (ACCESS: PROT, CFGLCK, AUTHLIM)
+    pub fn cfg1_page(self) -> u8 {
+        self.user_end() + 3
+    }
+
+    /// Hány lapot zárol egy dinamikus lock bit
+    pub fn pages_per_dynamic_lock_bit(self) -> u8 {
+        match self {
+            NtagChip::Ntag213 => 2,
+            NtagChip::Ntag215 | NtagChip::Ntag216 => 16,
+        }
+    }
+
+    /// Dinamikus lock bitek száma (16. laptól a felhasználói memória végéig)
+    pub fn dynamic_lock_bits(self) -> u8 {
+        let pages = self.user_end() - 16 + 1;
+        pages.div_ceil(self.pages_per_dynamic_lock_bit())
+    }
+
+    /// Hány dinamikus lock bitet fagyaszt be egy block-locking bit
+    pub fn lock_bits_per_block_lock_bit(self) -> u8 {
+        match self {
+            NtagChip::Ntag213 => 4,
+            NtagChip::Ntag215 | NtagChip::Ntag216 => 2,
+        }
+    }
+}
+
+/// Password lap valamelyik NTAG21x-en: az ide írt adat nem kerülhet a logba
+pub(crate) fn is_password_page(block: u8) -> bool {
+    [NtagChip::Ntag213, NtagChip::Ntag215, NtagChip::Ntag216]
+        .iter()
+        .any(|chip| chip.pwd_page() == block)
+}
+
+/// PWD_AUTH eredménye
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthOutcome {
+    /// Sikeres authentication, a címke által visszaadott PACK-kal
+    Ok { pack: [u8; 2] },
+    /// A címke elutasította a password-t (NAK)
+    WrongPassword,
+    /// A password elfogadva, de a PACK nem egyezik a várttal (hamisított vagy más címke)
+    PackMismatch { expected: [u8; 2], received: [u8; 2] },
+    /// Az olvasó nem tudja továbbítani a natív PWD_AUTH parancsot
+    Unsupported,
+}
+
+impl AuthOutcome {
+    /// Hibává alakítja a sikertelen eredményeket
+    pub fn ensure_ok(self) -> Result<()> {
+        match self {
+            AuthOutcome::Ok { .. } => Ok(()),
+            AuthOutcome::WrongPassword => Err(NfcError::WrongPassword),
+            AuthOutcome::PackMismatch { expected, received } => {
+                Err(NfcError::PackMismatch { expected, received })
+            }
+            AuthOutcome::Unsupported => Err(NfcError::AuthUnsupported),
+        }
+    }
+}
+
+/// Az olvasó nem ismeri a pseudo-APDU-t (CLA / INS / funkció nem támogatott)
+fn is_unsupported_command(sw1: u8, sw2: u8) -> bool {
+    matches!((sw1, sw2), (0x6A, 0x81) | (0x6D, 0x00) | (0x6E, 0x00) | (0x68, 0x00))
+}
+
+/// A kezdő lap a felhasználói memóriában van-e (4. laptól a chip utolsó felhasználói lapjáig)
+fn check_user_page(chip: NtagChip, block: u8) -> Result<()> {
+    if !(4..=chip.user_end()).contains(&block) {
+        return Err(NfcError::invalid_argument(format!(
+            "Érvénytelen block szám (4-{})",
+            chip.user_end()
+        )));
+    }
+    Ok(())
+}
+
+/// NDEF TLV hossz, amelytől a 3 byte-os forma kell (0xFF a rövid formában a 3 byte-os forma jelzője)
+const TLV_LONG_LENGTH: usize = 0xFF;
+
+/// NDEF TLV lapokra bontva a 4. laptól: `03 len` (0-254 byte) vagy `03 FF hi lo` (255 byte-tól),
+/// utána az üzenet 4 byte-os lapokra kiegészítve, majd a termináló TLV (0xFE) külön lapon.
+/// Írás előtt ellenőrizzük a kapacitást (4. laptól a chip utolsó felhasználói lapjáig),
+/// ne félúton derüljön ki.
+fn ndef_tlv_pages(chip: NtagChip, ndef_message: &[u8]) -> Result<Vec<[u8; 4]>> {
+    let tlv_length = ndef_message.len();
+    let mut data = if tlv_length < TLV_LONG_LENGTH {
+        vec![0x03, tlv_length as u8]
+    } else {
+        let length = u16::try_from(tlv_length)
+            .ok()
+            .filter(|&length| length != 0xFFFF)
+            .ok_or(NfcError::OutOfCapacity { size: tlv_length, max: 0xFFFE })?;
+        let [hi, lo] = length.to_be_bytes();
+        vec![0x03, 0xFF, hi, lo]
+    };
+    data.extend_from_slice(ndef_message);
+
+    let mut pages: Vec<[u8; 4]> = data
+        .chunks(4)
+        .map(|chunk| {
+            let mut page = [0u8; 4];
+            page[..chunk.len()].copy_from_slice(chunk);
+            page
+        })
+        .collect();
+    pages.push([0xFE, 0x00, 0x00, 0x00]);
+
+    let max_pages = (chip.user_end() - 4 + 1) as usize;
+    if pages.len() > max_pages {
+        return Err(NfcError::OutOfCapacity {
+            size: pages.len() * 4,
+            max: max_pages * 4,
+        });
+    }
+    Ok(pages)
+}
+
+/// NDEF TLV fejléc a 4. lapon: (üzenet hossz, fejléc hossz byte-ban), ha NDEF TLV
+fn parse_ndef_tlv_header(page: &[u8; 4]) -> Option<(usize, usize)> {
+    match page {
+        [0x03, 0xFF, hi, lo] => Some((u16::from_be_bytes([*hi, *lo]) as usize, 4)),
+        [0x03, length, _, _] => Some((*length as usize, 2)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chip_config_pages_follow_user_memory() {
+        // (chip, dinamikus lock, CFG0, CFG1, PWD, PACK)
+        let expected = [
+            (NtagChip::Ntag213, 0x28, 0x29, 0x2A, 0x2B, 0x2C),
+            (NtagChip::Ntag215, 0x82, 0x83, 0x84, 0x85, 0x86),
+            (NtagChip::Ntag216, 0xE2, 0xE3, 0xE4, 0xE5, 0xE6),
+        ];
+        for (chip, dynamic_lock, cfg0, cfg1, pwd, pack) in expected {
+            assert_eq!(chip.dynamic_lock_page(), dynamic_lock, "{:?}", chip);
+            assert_eq!(chip.cfg0_page(), cfg0, "{:?}", chip);
+            assert_eq!(chip.cfg1_page(), cfg1, "{:?}", chip);
+            assert_eq!(chip.pwd_page(), pwd, "{:?}", chip);
+            assert_eq!(chip.pack_page(), pack, "{:?}", chip);
+            assert!(is_password_page(pwd));
+        }
+        assert!(!is_password_page(0x04));
+        assert!(!is_password_page(NtagChip::Ntag216.cfg1_page()));
+    }
+
+    #[test]
+    fn chip_detection_from_cc_and_version() {
+        assert_eq!(NtagChip::from_cc(&[0xE1, 0x10, 0x12, 0x00]), Some(NtagChip::Ntag213));
+        assert_eq!(NtagChip::from_cc(&[0xE1, 0x10, 0x3E, 0x00]), Some(NtagChip::Ntag215));
+        assert_eq!(NtagChip::from_cc(&[0xE1, 0x10, 0x6D, 0x00]), Some(NtagChip::Ntag216));
+        assert_eq!(NtagChip::from_cc(&[0x00, 0x10, 0x6D, 0x00]), None);
+        assert_eq!(NtagChip::from_cc(&[0xE1, 0x10, 0x06, 0x00]), None);
+        assert_eq!(NtagChip::from_storage_size(0x13), Some(NtagChip::Ntag216));
+        assert_eq!(NtagChip::from_storage_size(0x0E), None);
+    }
+
+    #[test]
+    fn tlv_short_form_up_to_254_bytes() {
+        let pages = ndef_tlv_pages(NtagChip::Ntag216, &[0xAA; 254]).unwrap();
+        assert_eq!(pages[0], [0x03, 0xFE, 0xAA, 0xAA]);
+        assert_eq!(parse_ndef_tlv_header(&pages[0]), Some((254, 2)));
+        assert_eq!(pages.last(), Some(&[0xFE, 0x00, 0x00, 0x00]));
+    }
+
+    #[test]
+    fn tlv_long_form_from_255_bytes() {
+        let pages = ndef_tlv_pages(NtagChip::Ntag216, &[0xAA; 255]).unwrap();
+        assert_eq!(pages[0], [0x03, 0xFF, 0x00, 0xFF]);
+        assert_eq!(pages[1], [0xAA; 4]);
+        assert_eq!(parse_ndef_tlv_header(&pages[0]), Some((255, 4)));
+        // 4 byte fejléc + 255 byte üzenet 65 lapon, plusz a terminátor
+        assert_eq!(pages.len(), 66);
+        assert_eq!(pages[65], [0xFE, 0x00, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn tlv_rejects_message_beyond_user_memory() {
+        // Felhasználói lapok: NTAG213 36 (144 byte), NTAG215 126 (504 byte), NTAG216 222 (888 byte).
+        // Rövid formában 2 byte fejléc, 255 byte-tól 4; a terminátor külön lapot foglal.
+        let limits = [
+            (NtagChip::Ntag213, 138, 144),
+            (NtagChip::Ntag215, 496, 504),
+            (NtagChip::Ntag216, 880, 888),
+        ];
+        for (chip, max_message, max_bytes) in limits {
+            let pages = ndef_tlv_pages(chip, &vec![0u8; max_message]).unwrap();
+            assert_eq!(pages.len() * 4, max_bytes, "{:?}", chip);
+            match ndef_tlv_pages(chip, &vec![0u8; max_message + 1]) {
+                Err(NfcError::OutOfCapacity { max, .. }) => assert_eq!(max, max_bytes, "{:?}", chip),
+                other => panic!("{:?}: várt OutOfCapacity, kapott {:?}", chip, other),
+            }
+        }
+    }
+
+    #[test]
+    fn user_page_range_follows_chip() {
+        assert!(check_user_page(NtagChip::Ntag213, 0x27).is_ok());
+        assert!(check_user_page(NtagChip::Ntag213, 0x28).is_err());
+        assert!(check_user_page(NtagChip::Ntag216, 0xE1).is_ok());
+        assert!(check_user_page(NtagChip::Ntag216, 0xE2).is_err());
+        assert!(check_user_page(NtagChip::Ntag216, 3).is_err());
+    }
+
+    #[test]
+    fn tlv_header_rejects_other_tags() {
+        assert_eq!(parse_ndef_tlv_header(&[0x00, 0x03, 0x10, 0x00]), None);
+        assert_eq!(parse_ndef_tlv_header(&[0xFE, 0x00, 0x00, 0x00]), None);
+        assert_eq!(parse_ndef_tlv_header(&[0x03, 0x00, 0x00, 0x00]), Some((0, 2)));
+    }
+}
@@ -0,0 +1,132 @@
+use anyhow::{Context, Result};
+use pcsc::{Card, Context as PcscContext, Protocols, Scope, ShareMode};
+use serde::{Deserialize, Serialize};
+use std::ffi::CString;
+use tracing::{debug, warn};
+
+use super::error::NfcError;
+
+pub struct NfcReader {
+    ctx: PcscContext,
+}
+
+impl NfcReader {
+    pub fn new() -> Result<Self> {
+        let ctx = PcscContext::establish(Scope::User)
+            .context("Nem sikerült csatlakozni a PC/SC szolgáltatáshoz")?;
+        
+        Ok(NfcReader { ctx })
+    }
+
+    /// Csatlakozás a kiválasztott olvasón lévő címkéhez (None: az első olvasó)
+    pub fn connect(&self, selector: Option<&ReaderSelector>) -> Result<Card> {
+        debug!("Olvasók listázása...");
+        let readers = self.list_readers()?;
+        
+        debug!("Olvasók keresése...");
+        let reader = match selector {
+            Some(selector) => selector.resolve(&readers)?,
+            None => readers
+                .into_iter()
+                .next()
+                .ok_or_else(|| {
+                    warn!("Nincs NFC olvasó csatlakoztatva");
+                    NfcError::reader_missing("Nincs NFC olvasó csatlakoztatva")
+                })?,
+        };
+        
+        debug!("Olvasó találva: {:?}", reader);
+        debug!("Címke csatlakoztatása...");
+        let reader_name = CString::new(reader)
+            .context("Érvénytelen olvasó név")?;
+        let card = self.ctx.connect(&reader_name, ShareMode::Shared, Protocols::ANY)
+            .map_err(|e| match e {
+                pcsc::Error::NoSmartcard | pcsc::Error::RemovedCard => NfcError::NoTag.into(),
+                pcsc::Error::UnknownReader | pcsc::Error::ReaderUnavailable => {
+                    anyhow::Error::new(NfcError::reader_missing(format!("Az olvasó nem elérhető: {}", e)))
+                }
+                e => anyhow::Error::new(e)
+                    .context("Nem sikerült csatlakozni az NFC címkéhez. Kérlek helyezd a címkét az olvasóra."),
+            })?;
+        
+        debug!("Címke csatlakoztatva");
+        Ok(card)
+    }
+
+    /// Érvényes-e még a PC/SC kontextus (pl. pcscd újraindulás után nem)
+    pub fn is_valid(&self) -> bool {
+        self.ctx.is_valid().is_ok()
+    }
+
+    pub fn list_readers(&self) -> Result<Vec<String>> {
+        let mut buffer = [0u8; 2048];
+        let readers = self.ctx.list_readers(&mut buffer)
+            .context("Nem sikerült listázni az olvasókat")?;
+        
+        // ReaderNames már egy iterator-like struktúra, CStr-t String-gé konvertáljuk
+        let mut result = Vec::new();
+        for reader in readers {
+            result.push(reader.to_string_lossy().to_string());
+        }
+        Ok(result)
+    }
+}
+
+impl Default for NfcReader {
+    fn default() -> Self {
+        Self::new().expect("Nem sikerült inicializálni az NFC olvasót")
+    }
+}
+
+
+/// Olvasó kiválasztása név vagy sorszám alapján
+///
+/// JSON-ban szám (sorszám) vagy szöveg (név). A név lehet részlet is, pl. "ACR122",
+/// így a PC/SC által hozzáfűzött slot sorszám változása nem rontja el a beállítást.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ReaderSelector {
+    Index(usize),
+    Name(String),
+}
+
+impl ReaderSelector {
+    /// A kiválasztott olvasó teljes neve az elérhető olvasók közül
+    pub fn resolve(&self, readers: &[String]) -> Result<String> {
+        match self {
+            ReaderSelector::Index(index) => readers.get(*index).cloned().ok_or_else(|| {
+                NfcError::reader_missing(format!(
+                    "Nincs {}. sorszámú olvasó ({} olvasó csatlakoztatva)",
+                    index,
+                    readers.len()
+                ))
+                .into()
+            }),
+            ReaderSelector::Name(name) => {
+                if let Some(exact) = readers.iter().find(|r| *r == name) {
+                    return Ok(exact.clone());
+                }
+
+                let needle = name.to_lowercase();
+                let matches: Vec<&String> = readers
+                    .iter()
+                    .filter(|r| r.to_lowercase().contains(&needle))
+                    .collect();
+                match matches.as_slice() {
+                    [single] => Ok((*single).clone()),
+                    [] => Err(NfcError::reader_missing(format!(
+                        "A kiválasztott olvasó nem található: {}. Elérhető olvasók: {}",
+                        name,
+                        if readers.is_empty() { "nincs".to_string() } else { readers.join(", ") }
+                    ))
+                    .into()),
+                    _ => anyhow::bail!(
+                        "Több olvasó is illeszkedik erre: {} ({}). Add meg a teljes nevet!",
+                        name,
+                        matches.iter().map(|r| r.as_str()).collect::<Vec<_>>().join(", ")
+                    ),
+                }
+            }
+        }
+    }
+}
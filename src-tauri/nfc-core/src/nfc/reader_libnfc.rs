@@ -0,0 +1,598 @@
+use anyhow::Result;
+use std::ffi::{c_void, CString, CStr};
+use std::os::raw::{c_char, c_int};
+use std::ptr;
+use std::sync::OnceLock;
+use tracing::debug;
+
+use super::card_trait::Iso14443aTarget;
+use super::error::NfcError;
+use super::reader::ReaderSelector;
+use super::transport::{classify_libnfc, TransmitError};
+
+/// Egy connstring puffer mérete (nfc-types.h: NFC_BUFSIZE_CONNSTRING)
+const NFC_BUFSIZE_CONNSTRING: usize = 1024;
+/// Egy listázásban legfeljebb ennyi eszközt kérünk le
+const MAX_DEVICES: usize = 16;
+/// nfc-types.h: RF átviteli hiba (a PN53x timeout, CRC, paritás és keretezési hibái)
+const NFC_ERFTRANS: c_int = -20;
+
+type NfcConnstring = [c_char; NFC_BUFSIZE_CONNSTRING];
+
+// libnfc FFI kötések
+#[repr(C)]
+pub struct NfcDevice {
+    _private: [u8; 0],
+}
+
+#[repr(C)]
+pub struct NfcContext {
+    _private: [u8; 0],
+}
+
+// nfc-types.h: a struktúrák `#pragma pack(1)` alatt vannak definiálva, ezért packed
+// mirrorok. Az enum mezők C `int`-ek (a pack nem változtat a méretükön); Rust enum helyett
+// c_int, mert a libnfc által írt ismeretlen érték Rust enumban UB lenne.
+
+/// nfc_modulation_type
+pub type NfcModulationType = c_int;
+pub const NMT_ISO14443A: NfcModulationType = 1;
+
+/// nfc_baud_rate
+pub type NfcBaudRate = c_int;
+pub const NBR_106: NfcBaudRate = 1;
+
+#[repr(C, packed)]
+#[derive(Debug, Clone, Copy)]
+pub struct NfcModulation {
+    pub nmt: NfcModulationType,
+    pub nbr: NfcBaudRate,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub struct NfcIso14443aInfo {
+    pub atqa: [u8; 2],
+    pub sak: u8,
+    pub uid_len: usize,
+    pub uid: [u8; 10],
+    pub ats_len: usize,
+    pub ats: [u8; 254],
+}
+
+// A többi modulációhoz tartozó info csak az union méretéhez és a hű elrendezéshez kell
+#[allow(dead_code)]
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub struct NfcFelicaInfo {
+    pub len: usize,
+    pub res_code: u8,
+    pub id: [u8; 8],
+    pub pad: [u8; 8],
+    pub sys_code: [u8; 2],
+}
+
+#[allow(dead_code)]
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub struct NfcIso14443bInfo {
+    pub pupi: [u8; 4],
+    pub application_data: [u8; 4],
+    pub protocol_info: [u8; 3],
+    pub card_identifier: u8,
+}
+
+#[allow(dead_code)]
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub struct NfcIso14443biInfo {
+    pub div: [u8; 4],
+    pub ver_log: u8,
+    pub config: u8,
+    pub atr_len: usize,
+    pub atr: [u8; 33],
+}
+
+#[allow(dead_code)]
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub struct NfcIso14443b2srInfo {
+    pub uid: [u8; 8],
+}
+
+#[allow(dead_code)]
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub struct NfcIso14443b2ctInfo {
+    pub uid: [u8; 4],
+    pub prod_code: u8,
+    pub fab_code: u8,
+}
+
+#[allow(dead_code)]
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub struct NfcJewelInfo {
+    pub sens_res: [u8; 2],
+    pub id: [u8; 4],
+}
+
+#[allow(dead_code)]
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub struct NfcDepInfo {
+    pub nfcid3: [u8; 10],
+    pub did: u8,
+    pub bs: u8,
+    pub br: u8,
+    pub to: u8,
+    pub pp: u8,
+    pub gb: [u8; 48],
+    pub gb_len: usize,
+    /// nfc_dep_mode
+    pub ndm: c_int,
+}
+
+#[allow(dead_code)]
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub struct NfcBarcodeInfo {
+    pub data_len: usize,
+    pub data: [u8; 32],
+}
+
+#[allow(dead_code)]
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub struct NfcIso14443biclassInfo {
+    pub uid: [u8; 8],
+}
+
+/// nfc_target_info: a `nm.nmt` szerinti mező érvényes
+#[allow(dead_code)]
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub union NfcTargetInfo {
+    pub nai: NfcIso14443aInfo,
+    pub nfi: NfcFelicaInfo,
+    pub nbi: NfcIso14443bInfo,
+    pub nii: NfcIso14443biInfo,
+    pub nsi: NfcIso14443b2srInfo,
+    pub nci: NfcIso14443b2ctInfo,
+    pub nji: NfcJewelInfo,
+    pub ndi: NfcDepInfo,
+    pub nti: NfcBarcodeInfo,
+    pub nhi: NfcIso14443biclassInfo,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub struct NfcTarget {
+    pub nti: NfcTargetInfo,
+    pub nm: NfcModulation,
+}
+
+// Az elrendezés ellenőrzése: a legnagyobb union tag az nfc_iso14443a_info (283 byte 64 biten)
+const _: () = assert!(std::mem::size_of::<NfcIso14443aInfo>() == 3 + 10 + 254 + 2 * std::mem::size_of::<usize>());
+const _: () = assert!(std::mem::size_of::<NfcTarget>() == std::mem::size_of::<NfcIso14443aInfo>() + 8);
+
+impl NfcTarget {
+    /// Biztonságos másolat a libnfc által kitöltött célpontból (a hosszakat a tömbökre vágva)
+    fn iso14443a(&self) -> Option<Iso14443aTarget> {
+        let modulation = self.nm;
+        if modulation.nmt != NMT_ISO14443A {
+            return None;
+        }
+        // Az nmt alapján az nai mező érvényes; packed, ezért érték szerint másoljuk
+        let info = unsafe { self.nti.nai };
+        let (uid, uid_len, ats, ats_len) = (info.uid, info.uid_len, info.ats, info.ats_len);
+        Some(Iso14443aTarget {
+            uid: uid[..uid_len.min(uid.len())].to_vec(),
+            atqa: info.atqa,
+            sak: info.sak,
+            ats: ats[..ats_len.min(ats.len())].to_vec(),
+        })
+    }
+}
+
+/// A libnfc függvényei függvény pointerként: a könyvtárat futás közben töltjük be, így a
+/// build nem igényel libnfc-t, és ahol nincs telepítve, ott csak a libnfc háttér nem érhető el
+macro_rules! libnfc_api {
+    ($($name:ident: fn($($arg:ty),*) $(-> $ret:ty)?;)*) => {
+        struct LibnfcApi {
+            $($name: unsafe extern "C" fn($($arg),*) $(-> $ret)?,)*
+        }
+
+        impl LibnfcApi {
+            fn load(library: &dylib::Library) -> std::result::Result<Self, String> {
+                Ok(LibnfcApi {
+                    $($name: {
+                        let symbol = library.symbol(concat!(stringify!($name), "\0"));
+                        if symbol.is_null() {
+                            return Err(format!("A libnfc könyvtárból hiányzik: {}", stringify!($name)));
+                        }
+                        // A szimbólum a libnfc fejléc szerinti aláírású C függvény
+                        unsafe {
+                            std::mem::transmute::<*mut c_void, unsafe extern "C" fn($($arg),*) $(-> $ret)?>(symbol)
+                        }
+                    },)*
+                })
+            }
+        }
+    };
+}
+
+libnfc_api! {
+    nfc_init: fn(*mut *mut NfcContext) -> c_int;
+    nfc_exit: fn(*mut NfcContext);
+    nfc_list_devices: fn(*mut NfcContext, *mut NfcConnstring, usize) -> usize;
+    nfc_open: fn(*mut NfcContext, *const c_char) -> *mut NfcDevice;
+    nfc_close: fn(*mut NfcDevice);
+    nfc_initiator_init: fn(*mut NfcDevice) -> c_int;
+    nfc_initiator_select_passive_target: fn(*mut NfcDevice, NfcModulation, *const u8, usize, *mut NfcTarget) -> c_int;
+    nfc_initiator_transceive_bytes: fn(*mut NfcDevice, *const u8, usize, *mut u8, usize, c_int) -> c_int;
+    nfc_initiator_target_is_present: fn(*mut NfcDevice, *const NfcTarget) -> c_int;
+    nfc_device_get_name: fn(*const NfcDevice) -> *const c_char;
+    nfc_device_get_connstring: fn(*const NfcDevice) -> *const c_char;
+    nfc_strerror: fn(*const NfcDevice) -> *const c_char;
+}
+
+/// A libnfc megosztott könyvtár lehetséges nevei (a rendszer keresési útvonalán)
+#[cfg(target_os = "macos")]
+const LIBRARY_NAMES: &[&CStr] = &[
+    c"libnfc.6.dylib",
+    c"libnfc.dylib",
+    c"/opt/homebrew/lib/libnfc.6.dylib",
+    c"/usr/local/lib/libnfc.6.dylib",
+];
+#[cfg(windows)]
+const LIBRARY_NAMES: &[&CStr] = &[c"libnfc.dll", c"nfc.dll"];
+#[cfg(all(unix, not(target_os = "macos")))]
+const LIBRARY_NAMES: &[&CStr] = &[c"libnfc.so.6", c"libnfc.so"];
+
+static LIBNFC: OnceLock<std::result::Result<LibnfcApi, String>> = OnceLock::new();
+
+/// A libnfc betöltése (egyszer, az első használatkor). Ha nincs telepítve, `BackendUnavailable`.
+pub fn ensure_loaded() -> std::result::Result<(), NfcError> {
+    libnfc().map(|_| ())
+}
+
+fn libnfc() -> std::result::Result<&'static LibnfcApi, NfcError> {
+    LIBNFC
+        .get_or_init(|| {
+            let library = LIBRARY_NAMES
+                .iter()
+                .find_map(|name| dylib::Library::open(name))
+                .ok_or_else(|| "A libnfc nincs telepítve (lásd LIBNFC_SETUP.md)".to_string())?;
+            // A könyvtárat nem zárjuk be: a függvény pointerek a folyamat végéig érvényesek
+            let api = LibnfcApi::load(&library)?;
+            debug!("libnfc betöltve");
+            Ok(api)
+        })
+        .as_ref()
+        .map_err(|reason| NfcError::BackendUnavailable { reason: reason.clone() })
+}
+
+/// Megosztott könyvtár betöltése (dlopen / LoadLibrary)
+#[cfg(unix)]
+mod dylib {
+    use std::ffi::{c_void, CStr};
+
+    pub struct Library(*mut c_void);
+
+    impl Library {
+        pub fn open(name: &CStr) -> Option<Library> {
+            let handle = unsafe { libc::dlopen(name.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL) };
+            (!handle.is_null()).then_some(Library(handle))
+        }
+
+        /// `name` nullával lezárt szimbólum név; null pointer, ha nincs ilyen
+        pub fn symbol(&self, name: &str) -> *mut c_void {
+            unsafe { libc::dlsym(self.0, name.as_ptr().cast()) }
+        }
+    }
+}
+
+#[cfg(windows)]
+mod dylib {
+    use std::ffi::{c_void, CStr};
+    use std::os::raw::c_char;
+
+    extern "system" {
+        fn LoadLibraryA(name: *const c_char) -> *mut c_void;
+        fn GetProcAddress(module: *mut c_void, name: *const c_char) -> *mut c_void;
+    }
+
+    pub struct Library(*mut c_void);
+
+    impl Library {
+        pub fn open(name: &CStr) -> Option<Library> {
+            let handle = unsafe { LoadLibraryA(name.as_ptr()) };
+            (!handle.is_null()).then_some(Library(handle))
+        }
+
+        /// `name` nullával lezárt szimbólum név; null pointer, ha nincs ilyen
+        pub fn symbol(&self, name: &str) -> *mut c_void {
+            unsafe { GetProcAddress(self.0, name.as_ptr().cast()) }
+        }
+    }
+}
+
+pub struct LibnfcReader {
+    api: &'static LibnfcApi,
+    context: *mut NfcContext,
+    device: *mut NfcDevice,
+    /// A kiválasztott eszköz (None: a libnfc alapértelmezett eszköze)
+    selection: Option<ReaderSelector>,
+}
+
+// A libnfc kontextus és eszköz bármelyik szálról használható, csak egyszerre nem:
+// az alkalmazás Mutex mögött tartja az olvasót.
+unsafe impl Send for LibnfcReader {}
+
+impl LibnfcReader {
+    pub fn new() -> Result<Self> {
+        let api = libnfc()?;
+        
+        let mut context: *mut NfcContext = ptr::null_mut();
+        let result = unsafe { (api.nfc_init)(&mut context) };
+        
+        if result < 0 || context.is_null() {
+            anyhow::bail!("Nem sikerült inicializálni a libnfc kontextust");
+        }
+        
+        debug!("libnfc kontextus inicializálva");
+        Ok(LibnfcReader {
+            api,
+            context,
+            device: ptr::null_mut(),
+            selection: None,
+        })
+    }
+
+    /// Csatlakoztatott eszközök connstring-jei (pl. "acr122_usb:001:004", "pn53x_usb:002:003")
+    ///
+    /// A soros portos eszközöket (pn532_uart, ...) a libnfc csak "allow_intrusive_scan"
+    /// beállítással keresi, ezeket connstring-gel közvetlenül is ki lehet választani.
+    pub fn list_devices(&self) -> Vec<String> {
+        let mut connstrings: Vec<NfcConnstring> = vec![[0; NFC_BUFSIZE_CONNSTRING]; MAX_DEVICES];
+        let found = unsafe { (self.api.nfc_list_devices)(self.context, connstrings.as_mut_ptr(), MAX_DEVICES) };
+        connstrings
+            .iter()
+            .take(found.min(MAX_DEVICES))
+            .map(|connstring| unsafe { CStr::from_ptr(connstring.as_ptr()) }.to_string_lossy().to_string())
+            .collect()
+    }
+
+    /// Eszköz kiválasztása (connstring, részlet vagy sorszám); a megnyitott eszközt lezárja,
+    /// a következő `connect` már a kiválasztottat nyitja meg
+    pub fn restore_selection(&mut self, selection: Option<ReaderSelector>) {
+        self.close();
+        self.selection = selection;
+    }
+
+    pub fn selection(&self) -> Option<&ReaderSelector> {
+        self.selection.as_ref()
+    }
+
+    /// A kiválasztáshoz tartozó connstring. Ha a listában nem szerepel, de connstring
+    /// formájú ("driver:port"), változatlanul próbáljuk megnyitni.
+    fn resolve_connstring(&self) -> Result<Option<String>> {
+        let Some(selector) = &self.selection else {
+            return Ok(None);
+        };
+        match (selector.resolve(&self.list_devices()), selector) {
+            (Ok(connstring), _) => Ok(Some(connstring)),
+            (Err(_), ReaderSelector::Name(name)) if name.contains(':') => Ok(Some(name.clone())),
+            (Err(e), _) => Err(e),
+        }
+    }
+
+    pub fn connect(&mut self) -> Result<()> {
+        
+        if !self.device.is_null() {
+            return Ok(()); // Már csatlakoztatva van
+        }
+        
+        let connstring = self.resolve_connstring()?;
+        let c_connstring = connstring
+            .as_deref()
+            .map(CString::new)
+            .transpose()
+            .map_err(|_| NfcError::invalid_argument("Érvénytelen connstring"))?;
+        // NULL: a libnfc alapértelmezett eszköze (LIBNFC_DEFAULT_DEVICE vagy az első talált)
+        let device = unsafe {
+            (self.api.nfc_open)(self.context, c_connstring.as_ref().map_or(ptr::null(), |c| c.as_ptr()))
+        };
+        
+        if device.is_null() {
+            return Err(NfcError::reader_missing(match connstring {
+                Some(connstring) => format!("Nem sikerült megnyitni az NFC olvasót: {}", connstring),
+                None => "Nem sikerült megnyitni az NFC olvasót. Ellenőrizd a libnfc konfigurációt!".to_string(),
+            })
+            .into());
+        }
+        self.device = device;
+        
+        debug!(
+            "Olvasó találva: {} ({})",
+            self.device_name().unwrap_or_else(|| "Unknown".to_string()),
+            self.connstring().unwrap_or_default()
+        );
+        
+        let result = unsafe { (self.api.nfc_initiator_init)(self.device) };
+        if result < 0 {
+            self.close();
+            anyhow::bail!("Nem sikerült inicializálni az NFC iniciátort");
+        }
+        
+        debug!("NFC iniciátor inicializálva");
+        Ok(())
+    }
+
+    /// Az iniciátor újra inicializálása: a libnfc ilyenkor röviden kikapcsolja az RF mezőt,
+    /// így a HALT állapotú címke is újra kiválasztható
+    pub fn reset_field(&mut self) -> Result<()> {
+        if self.device.is_null() {
+            return Ok(());
+        }
+        let result = unsafe { (self.api.nfc_initiator_init)(self.device) };
+        if result < 0 {
+            self.close();
+            anyhow::bail!("Nem sikerült inicializálni az NFC iniciátort");
+        }
+        debug!("RF mező újraindítva");
+        Ok(())
+    }
+
+    /// A megnyitott eszköz lezárása (a kontextus megmarad)
+    pub fn close(&mut self) {
+        if !self.device.is_null() {
+            unsafe {
+                (self.api.nfc_close)(self.device);
+            }
+            self.device = ptr::null_mut();
+        }
+    }
+
+    /// A megnyitott eszköz neve
+    pub fn device_name(&self) -> Option<String> {
+        self.device_string(self.api.nfc_device_get_name)
+    }
+
+    /// A megnyitott eszköz connstring-je
+    pub fn connstring(&self) -> Option<String> {
+        self.device_string(self.api.nfc_device_get_connstring)
+    }
+
+    fn device_string(&self, getter: unsafe extern "C" fn(*const NfcDevice) -> *const c_char) -> Option<String> {
+        if self.device.is_null() {
+            return None;
+        }
+        let value = unsafe { getter(self.device) };
+        if value.is_null() {
+            return None;
+        }
+        Some(unsafe { CStr::from_ptr(value) }.to_string_lossy().to_string())
+    }
+
+    /// Egy ISO 14443-A (106 kbps) címke kiválasztása az olvasó mezejében
+    pub fn select_target(&mut self) -> Result<Iso14443aTarget> {
+        debug!("Címke csatlakoztatása...");
+        
+        // Csupa nulla: érvényes érték minden mezőre (egészek és byte tömbök)
+        let mut target: NfcTarget = unsafe { std::mem::zeroed() };
+        let modulation = NfcModulation {
+            nmt: NMT_ISO14443A,
+            nbr: NBR_106,
+        };
+        let result = unsafe {
+            (self.api.nfc_initiator_select_passive_target)(
+                self.device,
+                modulation,
+                ptr::null(),
+                0,
+                &mut target,
+            )
+        };
+        
+        if result < 0 {
+            anyhow::bail!("Nem sikerült csatlakozni az NFC címkéhez. Kérlek helyezd a címkét az olvasóra.");
+        }
+        if result == 0 {
+            return Err(NfcError::NoTag.into());
+        }
+        
+        let target = target
+            .iso14443a()
+            .ok_or_else(|| NfcError::invalid_response("A kiválasztott címke nem ISO 14443-A"))?;
+        debug!(
+            "Címke csatlakoztatva: UID {:02X?}, ATQA {:02X?}, SAK 0x{:02X}",
+            target.uid, target.atqa, target.sak
+        );
+        Ok(target)
+    }
+
+    /// Az utoljára kiválasztott címke még a mezőben van-e
+    pub fn is_target_present(&self) -> bool {
+        !self.device.is_null() && unsafe { (self.api.nfc_initiator_target_is_present)(self.device, ptr::null()) } == 0
+    }
+
+    pub fn transmit(&mut self, tx: &[u8]) -> Result<Vec<u8>> {
+        Ok(self.transmit_raw(tx)?)
+    }
+
+    /// Adat küldése a címkének, osztályozott hibával (az újrapróbáláshoz)
+    pub fn transmit_raw(&mut self, tx: &[u8]) -> Result<Vec<u8>, TransmitError> {
+        self.transceive(tx)
+            .map_err(|(code, message)| TransmitError::new(classify_libnfc(code), message))
+    }
+
+    /// Natív parancs, amelynél a címke hallgatása is eredmény (pl. PWD_AUTH): az RF átviteli
+    /// hibát (NFC_ERFTRANS: a címke nem válaszolt vagy NAK-ot adott) None jelzi, minden más
+    /// hiba (USB I/O, eltávolított címke) osztályozott TransmitError marad
+    pub fn transmit_native(&mut self, tx: &[u8]) -> Result<Option<Vec<u8>>, TransmitError> {
+        match self.transceive(tx) {
+            Ok(rx) => Ok(Some(rx)),
+            Err((NFC_ERFTRANS, message)) => {
+                debug!("A címke nem válaszolt: {}", message);
+                Ok(None)
+            }
+            Err((code, message)) => Err(TransmitError::new(classify_libnfc(code), message)),
+        }
+    }
+
+    /// nfc_initiator_transceive_bytes; hiba esetén a libnfc hibakód és az üzenet
+    fn transceive(&mut self, tx: &[u8]) -> Result<Vec<u8>, (c_int, String)> {
+        let mut rx = vec![0u8; 256];
+        let result = unsafe {
+            (self.api.nfc_initiator_transceive_bytes)(
+                self.device,
+                tx.as_ptr(),
+                tx.len(),
+                rx.as_mut_ptr(),
+                rx.len(),
+                500, // timeout ms
+            )
+        };
+        
+        if result < 0 {
+            let error_msg = unsafe {
+                let err_ptr = (self.api.nfc_strerror)(self.device);
+                if err_ptr.is_null() {
+                    "Unknown error"
+                } else {
+                    CStr::from_ptr(err_ptr).to_str().unwrap_or("Unknown error")
+                }
+            };
+            return Err((result, format!("libnfc transceive hiba: {} (code: {})", error_msg, result)));
+        }
+        
+        rx.truncate(result as usize);
+        Ok(rx)
+    }
+
+    /// Az elérhető eszközök connstring-jei. A megnyitott USB eszközt a libnfc nem listázza
+    /// újra (foglalt), ezért azt külön hozzáadjuk.
+    pub fn list_readers(&self) -> Result<Vec<String>> {
+        let mut readers = self.list_devices();
+        if let Some(connstring) = self.connstring() {
+            if !readers.contains(&connstring) {
+                readers.insert(0, connstring);
+            }
+        }
+        Ok(readers)
+    }
+}
+
+impl Drop for LibnfcReader {
+    fn drop(&mut self) {
+        self.close();
+        if !self.context.is_null() {
+            unsafe {
+                (self.api.nfc_exit)(self.context);
+            }
+        }
+    }
+}
+
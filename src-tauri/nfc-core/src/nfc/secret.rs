@@ -0,0 +1,24 @@
+use std::fmt;
+
+/// Titok (password, passphrase) a logban: csak a hossza látszik
+pub struct Secret<'a>(pub &'a [u8]);
+
+impl fmt::Debug for Secret<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<rejtett, {} byte>", self.0.len())
+    }
+}
+
+impl fmt::Display for Secret<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Natív címke parancs a logban: a PWD_AUTH (0x1B) password-je rejtve
+pub fn redact_native(command: &[u8]) -> String {
+    match command {
+        [0x1B, password @ ..] => format!("1B {}", Secret(password)),
+        _ => command.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(" "),
+    }
+}
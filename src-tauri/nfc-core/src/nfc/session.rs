@@ -0,0 +1,185 @@
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::debug;
+
+use super::backend::{Backend, Reader};
+use super::card_trait::{Iso14443aTarget, NfcCard};
+use super::driver::ReaderDriver;
+use super::reader::ReaderSelector;
+use super::trace::{ReplayCard, TraceRecorder, TracingCard};
+use super::transport::{RawTransmit, TransmitError};
+
+/// Tartós olvasó munkamenet
+///
+/// Az olvasó hátteret (PC/SC vagy libnfc) és a csatlakoztatott címkét megtartjuk a
+/// parancsok között, így az egymás utáni műveletek ugyanazt a kapcsolatot használják. Ha a
+/// címkét levették vagy a pcscd újraindult, a következő művelet automatikusan újracsatlakozik.
+#[derive(Default)]
+pub struct NfcSession {
+    backend: Backend,
+    reader: Option<Box<dyn Reader>>,
+    card: Option<Box<dyn NfcCard>>,
+    /// A kiválasztott olvasó (None: az első elérhető olvasó)
+    selection: Option<ReaderSelector>,
+    /// APDU trace felvétel: a csatlakozáskor a címkét rögzítő wrapperbe csomagoljuk
+    trace: Option<Arc<TraceRecorder>>,
+}
+
+impl NfcSession {
+    /// Érvényes olvasó háttér (szükség esetén újra létrehozva)
+    fn reader(&mut self) -> Result<&dyn Reader> {
+        let valid = self.reader.as_ref().map(|r| r.is_valid()).unwrap_or(false);
+        if !valid {
+            debug!("{} háttér létrehozása...", self.backend.label());
+            self.card = None;
+            self.reader = Some(self.backend.open()?);
+        }
+        self.reader
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("Nincs olvasó háttér"))
+    }
+
+    /// A csatlakoztatott címke; ha a korábbi kapcsolat megszakadt, újracsatlakozunk
+    pub fn card(&mut self) -> Result<&dyn NfcCard> {
+        let alive = self.card.as_ref().map(|c| c.is_present()).unwrap_or(false);
+        if !alive {
+            self.card = None;
+            let selection = self.selection.clone();
+            let card = self.reader()?.connect(selection.as_ref())?;
+            self.attach(card);
+        }
+        self.card
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("Nincs csatlakoztatott címke"))
+    }
+
+    /// Újracsatlakozás a címkéhez, ha NAK után HALT állapotba került (a kapcsolat él, de a
+    /// címke csak újbóli kiválasztás után válaszol)
+    pub fn reconnect(&mut self) -> Result<&dyn NfcCard> {
+        debug!("Újracsatlakozás a címkéhez (HALT után)...");
+        self.card = None;
+        let selection = self.selection.clone();
+        let card = self.reader()?.reconnect(selection.as_ref())?;
+        self.attach(card);
+        self.card
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("Nincs csatlakoztatott címke"))
+    }
+
+    /// A csatlakoztatott címke megtartása (trace felvételnél a rögzítő wrapperbe csomagolva)
+    /// az olvasó driverével, amelyet kapcsolatonként csak egyszer ismerünk fel
+    fn attach(&mut self, card: Box<dyn NfcCard>) {
+        let card = match &self.trace {
+            Some(recorder) => Box::new(TracingCard::new(card, recorder.clone())),
+            None => card,
+        };
+        self.card = Some(Box::new(SessionCard::new(card)));
+    }
+
+    /// Olvasó kiválasztása. A kiválasztást ellenőrizzük az aktuálisan csatlakoztatott
+    /// olvasókon, és visszaadjuk a teljes nevet (None esetén az első olvasó lesz használva).
+    pub fn select_reader(&mut self, selection: Option<ReaderSelector>) -> Result<Option<String>> {
+        let resolved = match &selection {
+            Some(selector) => Some(selector.resolve(&self.list_readers()?)?),
+            None => None,
+        };
+        self.card = None;
+        self.selection = selection;
+        Ok(resolved)
+    }
+
+    pub fn selection(&self) -> Option<&ReaderSelector> {
+        self.selection.as_ref()
+    }
+
+    /// Háttér váltás a háttérhez mentett olvasó kiválasztással, ellenőrzés nélkül (indításkor
+    /// az olvasó még nincs feltétlenül csatlakoztatva); a következő művelet már az új háttéren csatlakozik
+    pub fn set_backend(&mut self, backend: Backend, selection: Option<ReaderSelector>) {
+        self.card = None;
+        self.reader = None;
+        self.backend = backend;
+        self.selection = selection;
+    }
+
+    pub fn backend(&self) -> Backend {
+        self.backend
+    }
+
+    /// Annak az olvasónak a neve, amelyhez a címke csatlakozik
+    pub fn reader_name(&self) -> Option<String> {
+        self.card.as_ref()?.reader_name()
+    }
+
+    pub fn list_readers(&mut self) -> Result<Vec<String>> {
+        self.reader()?.list_readers()
+    }
+
+    /// APDU trace felvétel indítása; a következő csatlakozástól minden parancs a fájlba kerül
+    /// (a password byte-ok csak `plaintext_passwords` esetén nyersen)
+    pub fn start_trace(&mut self, path: &Path, plaintext_passwords: bool) -> Result<()> {
+        self.trace = Some(Arc::new(TraceRecorder::create(path, plaintext_passwords)?));
+        self.card = None;
+        Ok(())
+    }
+
+    /// A felvétel leállítása; a trace fájl útvonalát adja vissza
+    pub fn stop_trace(&mut self) -> Option<PathBuf> {
+        let recorder = self.trace.take()?;
+        self.card = None;
+        Some(recorder.path().to_path_buf())
+    }
+
+    pub fn trace_path(&self) -> Option<&Path> {
+        self.trace.as_ref().map(|recorder| recorder.path())
+    }
+
+    /// Rögzített trace visszajátszása: a következő műveletek a valódi címke helyett a
+    /// trace-t kapják, amíg el nem fogy
+    pub fn replay(&mut self, card: ReplayCard) {
+        self.card = Some(Box::new(SessionCard::new(Box::new(card))));
+    }
+}
+
+/// A munkamenet csatlakoztatott címkéje: a háttér címkéje a kapcsolathoz felismert
+/// olvasó driverrel (az ismeretlen ACS olvasók firmware lekérdezése így nem ismétlődik)
+struct SessionCard {
+    inner: Box<dyn NfcCard>,
+    driver: ReaderDriver,
+}
+
+impl SessionCard {
+    fn new(inner: Box<dyn NfcCard>) -> Self {
+        let driver = ReaderDriver::probe(inner.as_ref());
+        debug!("Olvasó driver: {}", driver.label());
+        SessionCard { inner, driver }
+    }
+}
+
+impl RawTransmit for SessionCard {
+    fn transmit_raw(&self, apdu: &[u8]) -> Result<Vec<u8>, TransmitError> {
+        self.inner.transmit_raw(apdu)
+    }
+}
+
+impl NfcCard for SessionCard {
+    fn backend(&self) -> Backend {
+        self.inner.backend()
+    }
+
+    fn reader_name(&self) -> Option<String> {
+        self.inner.reader_name()
+    }
+
+    fn is_present(&self) -> bool {
+        self.inner.is_present()
+    }
+
+    fn target(&self) -> Option<Iso14443aTarget> {
+        self.inner.target()
+    }
+
+    fn driver(&self) -> Option<ReaderDriver> {
+        Some(self.driver)
+    }
+}
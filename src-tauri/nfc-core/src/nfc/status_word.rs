@@ -0,0 +1,98 @@
+/// Status word (SW1 SW2) dekódolása emberi olvasható üzenetté
+///
+/// Források: ISO/IEC 7816-4, PC/SC Part 3 (tároló kártya parancsok: FF B0, FF D6, ...),
+/// valamint az ACR122U pseudo-APDU-k saját hibakódjai (63 01, 63 27, 63 7F).
+pub fn describe(sw1: u8, sw2: u8) -> String {
+    let text = match (sw1, sw2) {
+        (0x90, 0x00) => "Sikeres végrehajtás",
+        (0x61, n) => return format!("Sikeres, még {} byte válasz adat kérhető le", n),
+
+        // Figyelmeztetések (a címke állapota nem változott, vagy csak részben)
+        (0x62, 0x81) => "A visszaadott adat egy része sérült lehet",
+        (0x62, 0x82) => "Az adat vége előbb elérve, mint a kért hossz",
+        (0x62, 0x83) => "A kiválasztott fájl érvénytelenítve",
+        (0x63, 0x00) => "A művelet sikertelen: a címke NAK-ot adott vagy nem válaszolt (pl. password védett lap)",
+        // ACR122U: a PN532 hibája a pseudo-APDU feldolgozása közben
+        (0x63, 0x01) => "Az olvasó NFC chipje (PN532) nem válaszolt",
+        (0x63, 0x27) => "Ellenőrzőösszeg hiba a PN532 válaszában",
+        (0x63, 0x7F) => "Hibás PN532 parancs",
+        (0x63, n) if n & 0xF0 == 0xC0 => {
+            return format!("Authentication sikertelen, még {} próbálkozás maradt", n & 0x0F)
+        }
+        (0x63, _) => "Authentication sikertelen",
+
+        // Végrehajtási hibák
+        (0x64, 0x00) => "Végrehajtási hiba: a címke nem válaszolt időben (RF hiba / timeout)",
+        (0x65, 0x81) => "Memória hiba: az írás nem sikerült (a címke eltávolítva vagy sérült)",
+        (0x65, _) => "Memória hiba",
+
+        // Ellenőrzési hibák
+        (0x67, 0x00) => "Hibás hossz (Lc/Le)",
+        (0x68, 0x00) => "A CLA byte nem támogatott",
+        (0x68, 0x81) => "Logikai csatorna nem támogatott",
+        (0x68, 0x82) => "Secure messaging nem támogatott",
+        (0x69, 0x81) => "A parancs nem kompatibilis a címkével (zárolt vagy nem írható lap)",
+        (0x69, 0x82) => "Biztonsági feltétel nem teljesül: authentication szükséges",
+        (0x69, 0x83) => "Az authentication blokkolva (elfogyott a próbálkozások száma)",
+        (0x69, 0x84) => "A hivatkozott adat érvénytelen",
+        (0x69, 0x85) => "A használati feltételek nem teljesülnek",
+        (0x69, 0x86) => "A parancs nem engedélyezett (zárolt lap)",
+        (0x6A, 0x80) => "Hibás adat a parancsban",
+        (0x6A, 0x81) => "A funkció nem támogatott (az olvasó vagy a címke nem ismeri)",
+        (0x6A, 0x82) => "A megcímzett lap/byte nem létezik",
+        (0x6A, 0x86) => "Hibás P1/P2 paraméter",
+        (0x6B, 0x00) => "Hibás paraméter: a cím kívül esik a címke memóriáján",
+        (0x6C, n) => return format!("Hibás Le, a helyes hossz: {} byte", n),
+        (0x6D, 0x00) => "Az INS byte nem támogatott",
+        (0x6E, 0x00) => "A CLA byte nem támogatott",
+        (0x6F, 0x00) => "Ismeretlen hiba (nincs pontos diagnózis)",
+        _ => "Ismeretlen status word",
+    };
+    text.to_string()
+}
+
+/// PN532/PN533 hibakód (InCommunicateThru / InDataExchange válasz status byte-ja, alsó 6 bit)
+pub fn describe_pn533_status(status: u8) -> &'static str {
+    match status & 0x3F {
+        0x00 => "Sikeres",
+        0x01 => "Timeout: a címke nem válaszolt",
+        0x02 => "CRC hiba",
+        0x03 => "Paritás hiba",
+        0x04 => "Hibás bit szám (antikollízió)",
+        0x05 => "Keretezési hiba",
+        0x06 => "Bit ütközés (több címke a mezőben?)",
+        0x07 => "Elégtelen puffer méret",
+        0x09 => "RF puffer túlcsordulás",
+        0x0A => "Az RF mező nem kapcsolt be időben",
+        0x0B => "RF protokoll hiba",
+        0x0D => "Túlmelegedés",
+        0x0E => "Belső puffer túlcsordulás",
+        0x10 => "Érvénytelen paraméter",
+        0x12 => "DEP: nem támogatott parancs",
+        0x13 => "DEP: hibás adat formátum",
+        0x14 => "Authentication hiba",
+        0x23 => "Hibás UID ellenőrző byte",
+        0x25 => "Érvénytelen eszköz állapot",
+        0x26 => "A művelet nem engedélyezett ebben a konfigurációban",
+        0x27 => "A parancs ebben a helyzetben nem elfogadható",
+        0x29 => "A címkét elengedte az iniciátor",
+        0x2A => "A címke azonosító nem egyezik",
+        0x2B => "A címke eltűnt a mezőből",
+        0x2C => "NFCID3 eltérés",
+        0x2D => "Túláram",
+        0x2E => "Hiányzó NAD a DEP keretben",
+        _ => "Ismeretlen PN533 hibakód",
+    }
+}
+
+/// NFC Forum Type 2 (NTAG) 4 bites ACK/NAK válasz
+pub fn describe_type2_ack(nibble: u8) -> &'static str {
+    match nibble & 0x0F {
+        0x0A => "ACK",
+        0x00 => "NAK a címkétől: érvénytelen argumentum (pl. érvénytelen lap cím vagy hibás password)",
+        0x01 => "NAK a címkétől: paritás vagy CRC hiba",
+        0x04 => "NAK a címkétől: az authentication számláló (AUTHLIM) túlcsordult",
+        0x05 => "NAK a címkétől: EEPROM írási hiba",
+        _ => "NAK a címkétől: ismeretlen kód",
+    }
+}
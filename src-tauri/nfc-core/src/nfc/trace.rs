@@ -0,0 +1,459 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use tracing::{debug, warn};
+
+use super::backend::Backend;
+use super::card_trait::{Iso14443aTarget, NfcCard};
+use super::driver::ReaderDriver;
+use super::ntag216::is_password_page;
+use super::transport::{RawTransmit, TransmitError, TransmitErrorKind};
+
+/// A trace fájl formátum verziója
+const TRACE_VERSION: u32 = 1;
+
+/// A kitakart password byte-ok jelölője a parancs hex formájában
+const REDACTED: &str = "**";
+
+/// Egy sor a trace fájlban (JSON lines): a címke csatlakozásakor egy fejléc, utána minden
+/// olvasónak küldött APDU a válasszal vagy a hibával
+///
+/// A PWD_AUTH és a PWD lap írásának password byte-jai alapból `**` jelölővel kerülnek a
+/// fájlba; nyersen csak kifejezett kérésre (`TraceRecorder::create`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TraceLine {
+    Header {
+        version: u32,
+        /// Unix idő ezredmásodpercben
+        started_ms: u64,
+        backend: Backend,
+        reader: Option<String>,
+        target: Option<Iso14443aTarget>,
+        /// A password byte-ok ki vannak-e takarva (a régi, jelölő nélküli trace-ekben nem)
+        #[serde(default)]
+        passwords_redacted: bool,
+    },
+    Exchange {
+        /// A felvétel kezdete óta eltelt idő mikroszekundumban
+        elapsed_us: u64,
+        /// APDU (hex, a kitakart password byte-ok helyén `**`)
+        command: String,
+        /// Nyers válasz status word-del (hex); hiba esetén None
+        response: Option<String>,
+        error: Option<TraceError>,
+    },
+}
+
+/// Rögzített kommunikációs hiba
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceError {
+    pub kind: TransmitErrorKind,
+    pub message: String,
+}
+
+/// APDU trace felvétel fájlba. Minden sort azonnal kiírunk, hogy összeomlás után is
+/// meglegyen a hibához vezető kommunikáció.
+pub struct TraceRecorder {
+    path: PathBuf,
+    writer: Mutex<BufWriter<File>>,
+    started: Instant,
+    /// A password byte-ok nyersen kerülnek a fájlba (csak kifejezett kérésre)
+    plaintext_passwords: bool,
+}
+
+impl TraceRecorder {
+    /// Új felvétel; `plaintext_passwords` nélkül a password byte-ok ki vannak takarva
+    pub fn create(path: &Path, plaintext_passwords: bool) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Nem sikerült létrehozni a könyvtárat: {}", parent.display()))?;
+        }
+        let file = File::create(path)
+            .with_context(|| format!("Nem sikerült létrehozni a trace fájlt: {}", path.display()))?;
+        debug!("APDU trace felvétel: {}", path.display());
+        if plaintext_passwords {
+            warn!("A trace a password-öket nyersen tartalmazza: {}", path.display());
+        }
+        Ok(TraceRecorder {
+            path: path.to_path_buf(),
+            writer: Mutex::new(BufWriter::new(file)),
+            started: Instant::now(),
+            plaintext_passwords,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Egy sor kiírása; az írási hiba nem szakíthatja meg a címke műveletet
+    fn write(&self, line: &TraceLine) {
+        let mut writer = self.writer.lock().unwrap();
+        let result = serde_json::to_writer(&mut *writer, line)
+            .map_err(std::io::Error::from)
+            .and_then(|_| writer.write_all(b"\n"))
+            .and_then(|_| writer.flush());
+        if let Err(e) = result {
+            warn!("Trace írási hiba ({}): {}", self.path.display(), e);
+        }
+    }
+}
+
+/// Címke, amelynek minden APDU-ját (újrapróbálásokkal és olvasó parancsokkal együtt) rögzítjük
+pub struct TracingCard {
+    inner: Box<dyn NfcCard>,
+    recorder: Arc<TraceRecorder>,
+}
+
+impl TracingCard {
+    pub fn new(inner: Box<dyn NfcCard>, recorder: Arc<TraceRecorder>) -> Self {
+        recorder.write(&TraceLine::Header {
+            version: TRACE_VERSION,
+            started_ms: unix_millis(),
+            backend: inner.backend(),
+            reader: inner.reader_name(),
+            target: inner.target(),
+            passwords_redacted: !recorder.plaintext_passwords,
+        });
+        TracingCard { inner, recorder }
+    }
+}
+
+impl RawTransmit for TracingCard {
+    fn transmit_raw(&self, apdu: &[u8]) -> Result<Vec<u8>, TransmitError> {
+        let result = self.inner.transmit_raw(apdu);
+        self.recorder.write(&TraceLine::Exchange {
+            elapsed_us: self.recorder.started.elapsed().as_micros() as u64,
+            command: pattern_hex(&command_pattern(apdu, !self.recorder.plaintext_passwords)),
+            response: result.as_ref().ok().map(|response| to_hex(response)),
+            error: result.as_ref().err().map(|e| TraceError {
+                kind: e.kind,
+                message: e.message.clone(),
+            }),
+        });
+        result
+    }
+}
+
+impl NfcCard for TracingCard {
+    fn backend(&self) -> Backend {
+        self.inner.backend()
+    }
+
+    fn reader_name(&self) -> Option<String> {
+        self.inner.reader_name()
+    }
+
+    fn is_present(&self) -> bool {
+        self.inner.is_present()
+    }
+
+    fn target(&self) -> Option<Iso14443aTarget> {
+        self.inner.target()
+    }
+
+    fn driver(&self) -> Option<ReaderDriver> {
+        self.inner.driver()
+    }
+}
+
+/// Rögzített trace visszajátszása címkeként: a parancsoknak pontosan a felvett sorrendben
+/// kell érkezniük, a válasz a felvett válasz (vagy hiba)
+///
+/// A trace végén a címke "eltűnik", a munkamenet ilyenkor újra a valódi olvasóhoz csatlakozik.
+pub struct ReplayCard {
+    backend: Backend,
+    reader: Option<String>,
+    target: Option<Iso14443aTarget>,
+    /// A felvételen ki vannak takarva a password byte-ok: a kapott parancsot is kitakarva hasonlítjuk
+    passwords_redacted: bool,
+    exchanges: Vec<ReplayExchange>,
+    position: Mutex<usize>,
+}
+
+/// Egy felvett APDU és a rá kapott válasz
+struct ReplayExchange {
+    command: CommandPattern,
+    response: Result<Vec<u8>, TransmitError>,
+}
+
+impl ReplayCard {
+    /// Trace betöltése; több fejléc esetén (több címke) az első címke kommunikációját játsszuk vissza
+    pub fn load(path: &Path) -> Result<Self> {
+        let file = File::open(path).with_context(|| format!("Nem sikerült megnyitni a trace fájlt: {}", path.display()))?;
+        let mut header = None;
+        let mut exchanges = Vec::new();
+
+        for (number, line) in BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let parsed: TraceLine =
+                serde_json::from_str(&line).with_context(|| format!("Érvénytelen trace sor ({}. sor)", number + 1))?;
+            match parsed {
+                TraceLine::Header { version, backend, reader, target, passwords_redacted, .. } => {
+                    if header.is_some() {
+                        break;
+                    }
+                    if version != TRACE_VERSION {
+                        anyhow::bail!("Nem támogatott trace verzió: {}", version);
+                    }
+                    header = Some((backend, reader, target, passwords_redacted));
+                }
+                TraceLine::Exchange { command, response, error, .. } => {
+                    let response = match (response, error) {
+                        (Some(response), _) => Ok(from_hex(&response)?),
+                        (None, Some(error)) => Err(TransmitError::new(error.kind, error.message)),
+                        (None, None) => anyhow::bail!("Válasz nélküli trace sor ({}. sor)", number + 1),
+                    };
+                    exchanges.push(ReplayExchange {
+                        command: parse_pattern(&command)?,
+                        response,
+                    });
+                }
+            }
+        }
+
+        let (backend, reader, target, passwords_redacted) =
+            header.ok_or_else(|| anyhow::anyhow!("A trace fájlban nincs fejléc"))?;
+        debug!("APDU trace visszajátszás: {} ({} parancs)", path.display(), exchanges.len());
+        Ok(ReplayCard {
+            backend,
+            reader,
+            target,
+            passwords_redacted,
+            exchanges,
+            position: Mutex::new(0),
+        })
+    }
+}
+
+impl RawTransmit for ReplayCard {
+    fn transmit_raw(&self, apdu: &[u8]) -> Result<Vec<u8>, TransmitError> {
+        let mut position = self.position.lock().unwrap();
+        let Some(exchange) = self.exchanges.get(*position) else {
+            return Err(TransmitError::new(TransmitErrorKind::CardRemoved, "A trace véget ért"));
+        };
+        // A kapott parancsot ugyanúgy takarjuk ki, mint felvételkor: a password nem kerül a hibaüzenetbe
+        let command = command_pattern(apdu, self.passwords_redacted);
+        if exchange.command != command {
+            return Err(TransmitError::new(
+                TransmitErrorKind::Protocol,
+                format!(
+                    "Eltérés a trace-től a(z) {}. parancsnál: várt {}, kapott {}",
+                    *position + 1,
+                    pattern_hex(&exchange.command),
+                    pattern_hex(&command)
+                ),
+            ));
+        }
+        *position += 1;
+        exchange.response.clone()
+    }
+}
+
+impl NfcCard for ReplayCard {
+    fn backend(&self) -> Backend {
+        self.backend
+    }
+
+    fn reader_name(&self) -> Option<String> {
+        self.reader.clone()
+    }
+
+    fn is_present(&self) -> bool {
+        *self.position.lock().unwrap() < self.exchanges.len()
+    }
+
+    fn target(&self) -> Option<Iso14443aTarget> {
+        self.target.clone()
+    }
+}
+
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Egy parancs byte-jai a trace-ben; None: kitakart password byte
+type CommandPattern = Vec<Option<u8>>;
+
+/// A password byte-ok helye az APDU-ban: PWD_AUTH (1B) és a PWD lap írása (FF D6, illetve natív
+/// A2), közvetlenül vagy az olvasó keretezésében (InCommunicateThru, Direct Transmit,
+/// Transparent Exchange)
+fn password_range(apdu: &[u8]) -> Option<Range<usize>> {
+    if let [0xFF, 0xD6, 0x00, page, 0x04, ..] = apdu {
+        return (is_password_page(*page) && apdu.len() >= 9).then_some(5..9);
+    }
+    let offset = match apdu {
+        [0xFF, 0x00, 0x00, 0x00, _, 0xD4, 0x42, ..] => 7,
+        [0xFF, 0xC2, 0x00, 0x01, _, 0x95, _, ..] => 7,
+        [0xFF, 0x00, 0x00, 0x00, _, ..] => 5,
+        _ => return None,
+    };
+    let range = match apdu[offset..] {
+        [0x1B, ..] => offset + 1..offset + 5,
+        [0xA2, page, ..] if is_password_page(page) => offset + 2..offset + 6,
+        _ => return None,
+    };
+    (range.end <= apdu.len()).then_some(range)
+}
+
+/// Az APDU a trace-be írandó formában; `redact` esetén a password byte-ok kitakarva
+fn command_pattern(apdu: &[u8], redact: bool) -> CommandPattern {
+    let hidden = if redact { password_range(apdu) } else { None };
+    apdu.iter()
+        .enumerate()
+        .map(|(index, byte)| match &hidden {
+            Some(range) if range.contains(&index) => None,
+            _ => Some(*byte),
+        })
+        .collect()
+}
+
+fn pattern_hex(pattern: &[Option<u8>]) -> String {
+    pattern
+        .iter()
+        .map(|byte| match byte {
+            Some(byte) => format!("{:02X}", byte),
+            None => REDACTED.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn parse_pattern(hex: &str) -> Result<CommandPattern> {
+    let mut pattern = Vec::new();
+    for token in hex.split_whitespace() {
+        if token == REDACTED {
+            pattern.push(None);
+        } else {
+            pattern.extend(from_hex(token)?.into_iter().map(Some));
+        }
+    }
+    Ok(pattern)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(" ")
+}
+
+fn from_hex(hex: &str) -> Result<Vec<u8>> {
+    let digits: Vec<char> = hex.chars().filter(|c| !c.is_whitespace()).collect();
+    if !digits.len().is_multiple_of(2) {
+        anyhow::bail!("Páratlan számú hex karakter: {}", hex);
+    }
+    digits
+        .chunks(2)
+        .map(|pair| {
+            let byte: String = pair.iter().collect();
+            u8::from_str_radix(&byte, 16).with_context(|| format!("Érvénytelen hex byte: {}", byte))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PWD: [u8; 4] = [0x12, 0x34, 0x56, 0x78];
+
+    fn with_pwd(prefix: &[u8]) -> Vec<u8> {
+        let mut apdu = prefix.to_vec();
+        apdu.extend_from_slice(&PWD);
+        apdu
+    }
+
+    #[test]
+    fn pwd_auth_is_redacted_in_every_framing() {
+        let direct = with_pwd(&[0xFF, 0x00, 0x00, 0x00, 0x05, 0x1B]);
+        assert_eq!(pattern_hex(&command_pattern(&direct, true)), "FF 00 00 00 05 1B ** ** ** **");
+
+        let thru = with_pwd(&[0xFF, 0x00, 0x00, 0x00, 0x07, 0xD4, 0x42, 0x1B]);
+        assert_eq!(pattern_hex(&command_pattern(&thru, true)), "FF 00 00 00 07 D4 42 1B ** ** ** **");
+
+        let mut transparent = with_pwd(&[0xFF, 0xC2, 0x00, 0x01, 0x07, 0x95, 0x05, 0x1B]);
+        transparent.push(0x00);
+        assert_eq!(pattern_hex(&command_pattern(&transparent, true)), "FF C2 00 01 07 95 05 1B ** ** ** ** 00");
+    }
+
+    #[test]
+    fn pwd_page_write_is_redacted() {
+        // NTAG216 PWD lap: E5h
+        let update = with_pwd(&[0xFF, 0xD6, 0x00, 0xE5, 0x04]);
+        assert_eq!(pattern_hex(&command_pattern(&update, true)), "FF D6 00 E5 04 ** ** ** **");
+
+        let native = with_pwd(&[0xFF, 0x00, 0x00, 0x00, 0x06, 0xA2, 0xE5]);
+        assert_eq!(pattern_hex(&command_pattern(&native, true)), "FF 00 00 00 06 A2 E5 ** ** ** **");
+    }
+
+    #[test]
+    fn other_commands_and_opt_in_stay_plaintext() {
+        let user_page = with_pwd(&[0xFF, 0xD6, 0x00, 0x04, 0x04]);
+        assert_eq!(pattern_hex(&command_pattern(&user_page, true)), "FF D6 00 04 04 12 34 56 78");
+
+        let direct = with_pwd(&[0xFF, 0x00, 0x00, 0x00, 0x05, 0x1B]);
+        assert_eq!(pattern_hex(&command_pattern(&direct, false)), "FF 00 00 00 05 1B 12 34 56 78");
+        assert_eq!(parse_pattern("FF 1B ** ** ** **").unwrap(), vec![Some(0xFF), Some(0x1B), None, None, None, None]);
+    }
+
+    fn replay(name: &str, lines: &[&str]) -> ReplayCard {
+        let path = std::env::temp_dir().join(format!("nfc-trace-{}-{}.jsonl", name, std::process::id()));
+        fs::write(&path, lines.join("\n")).unwrap();
+        let card = ReplayCard::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        card
+    }
+
+    #[test]
+    fn replay_matches_redacted_pwd_auth() {
+        let card = replay(
+            "redacted",
+            &[
+                r#"{"type":"header","version":1,"started_ms":0,"backend":"pcsc","reader":null,"target":null,"passwords_redacted":true}"#,
+                r#"{"type":"exchange","elapsed_us":0,"command":"FF 00 00 00 05 1B ** ** ** **","response":"80 80 90 00","error":null}"#,
+            ],
+        );
+        let response = card.transmit_raw(&with_pwd(&[0xFF, 0x00, 0x00, 0x00, 0x05, 0x1B])).unwrap();
+        assert_eq!(response, vec![0x80, 0x80, 0x90, 0x00]);
+        assert!(!card.is_present());
+    }
+
+    #[test]
+    fn replay_mismatch_is_protocol_error_without_password() {
+        let card = replay(
+            "mismatch",
+            &[
+                r#"{"type":"header","version":1,"started_ms":0,"backend":"pcsc","reader":null,"target":null,"passwords_redacted":true}"#,
+                r#"{"type":"exchange","elapsed_us":0,"command":"FF B0 00 04 10","response":"90 00","error":null}"#,
+            ],
+        );
+        let error = card.transmit_raw(&with_pwd(&[0xFF, 0x00, 0x00, 0x00, 0x05, 0x1B])).unwrap_err();
+        assert_eq!(error.kind, TransmitErrorKind::Protocol);
+        assert!(error.message.contains("1B ** ** ** **"), "{}", error.message);
+        assert!(!error.message.contains("12 34 56 78"));
+        // Az eltérő parancs nem fogyasztja a felvételt
+        assert!(card.transmit_raw(&[0xFF, 0xB0, 0x00, 0x04, 0x10]).is_ok());
+    }
+
+    #[test]
+    fn old_traces_replay_plaintext() {
+        let card = replay(
+            "plaintext",
+            &[
+                r#"{"type":"header","version":1,"started_ms":0,"backend":"pcsc","reader":null,"target":null}"#,
+                r#"{"type":"exchange","elapsed_us":0,"command":"FF 00 00 00 05 1B 12 34 56 78","response":"80 80 90 00","error":null}"#,
+            ],
+        );
+        assert!(card.transmit_raw(&with_pwd(&[0xFF, 0x00, 0x00, 0x00, 0x05, 0x1B])).is_ok());
+    }
+}
@@ -0,0 +1,147 @@
+use super::driver::{tag_response, NativeResponse};
+use super::error::{NfcError, Result};
+use super::status_word;
+use super::transport::{self, RawTransmit, RetryPolicy};
+
+/// PC/SC 2.01 Part 3 pseudo-APDU: FF C2 00 [P2]
+const MANAGE_SESSION: u8 = 0x00;
+const TRANSPARENT_EXCHANGE: u8 = 0x01;
+
+/// Adat objektumok (PC/SC Part 3, 3.2.1.1 és 3.2.1.2)
+const START_SESSION: u8 = 0x81;
+const END_SESSION: u8 = 0x82;
+const TRANSCEIVE: u8 = 0x95;
+const GENERIC_ERROR_STATUS: u8 = 0xC0;
+const RESPONSE_DATA: u8 = 0x97;
+
+/// Transparent exchange session: a nyers ISO 14443-3 parancsok a címkéhez jutnak
+/// az olvasó saját (CRC, keretezés) kezelésével, olvasó-specifikus escape parancsok nélkül
+///
+/// A session a `start` hívással nyílik és eldobáskor zárul.
+/// Session közben az olvasó nem küld automatikus parancsokat a címkének
+/// (pl. jelenlét ellenőrzést), így a natív parancsok sorrendje nem keveredik.
+pub struct TransparentSession<'a, T: RawTransmit + ?Sized> {
+    card: &'a T,
+}
+
+impl<'a, T: RawTransmit + ?Sized> TransparentSession<'a, T> {
+    /// Session nyitása. Ha az olvasó nem ismeri az FF C2 parancsot, status word hibát ad
+    /// (tipikusan 6A 81 vagy 6D 00).
+    pub fn start(card: &'a T) -> Result<Self> {
+        manage_session(card, START_SESSION)?;
+        Ok(TransparentSession { card })
+    }
+
+    /// Egy natív parancs küldése és a címke válasza
+    pub fn transceive(&self, command: &[u8], policy: &RetryPolicy) -> Result<NativeResponse> {
+        let response = transport::transmit_with_retry(self.card, &transceive_apdu(command), policy)?;
+        parse_exchange(&response.into_data()?)
+    }
+}
+
+impl<T: RawTransmit + ?Sized> Drop for TransparentSession<'_, T> {
+    fn drop(&mut self) {
+        let _ = manage_session(self.card, END_SESSION);
+    }
+}
+
+/// Transparent Exchange APDU egyetlen Transceive adat objektummal
+pub fn transceive_apdu(command: &[u8]) -> Vec<u8> {
+    let mut data = vec![TRANSCEIVE, command.len() as u8];
+    data.extend_from_slice(command);
+    pseudo_apdu(TRANSPARENT_EXCHANGE, &data)
+}
+
+/// A Transparent Exchange válaszának feldolgozása (a status word már ellenőrizve)
+pub fn parse_exchange(data: &[u8]) -> Result<NativeResponse> {
+    let objects = parse_data_objects(data)?;
+    if let Some(reason) = generic_error(&objects) {
+        return Ok(NativeResponse::NoResponse { reason });
+    }
+    match objects.iter().find(|(tag, _)| *tag == RESPONSE_DATA) {
+        Some((_, reply)) => Ok(tag_response(reply)),
+        None => Ok(NativeResponse::NoResponse {
+            reason: "A transparent exchange válasz nem tartalmaz adatot".to_string(),
+        }),
+    }
+}
+
+fn pseudo_apdu(p2: u8, data: &[u8]) -> Vec<u8> {
+    let mut apdu = vec![0xFF, 0xC2, 0x00, p2, data.len() as u8];
+    apdu.extend_from_slice(data);
+    apdu.push(0x00);
+    apdu
+}
+
+fn manage_session<T: RawTransmit + ?Sized>(card: &T, object: u8) -> Result<()> {
+    let apdu = pseudo_apdu(MANAGE_SESSION, &[object, 0x00]);
+    let response = transport::transmit_with_retry(card, &apdu, &transport::retry_policy())?;
+    let data = response.into_data()?;
+    match generic_error(&parse_data_objects(&data)?) {
+        Some(reason) => Err(NfcError::invalid_response(reason)),
+        None => Ok(()),
+    }
+}
+
+/// Generic error status (C0): [hibás adat objektum sorszáma] [SW1] [SW2], 00 90 00 = sikeres
+fn generic_error(objects: &[(u8, &[u8])]) -> Option<String> {
+    objects.iter().find_map(|(tag, value)| match (*tag, *value) {
+        (GENERIC_ERROR_STATUS, [index, sw1, sw2, ..]) if (*sw1, *sw2) != (0x90, 0x00) => Some(format!(
+            "Transparent exchange hiba a(z) {}. adat objektumnál: {}",
+            index,
+            status_word::describe(*sw1, *sw2)
+        )),
+        _ => None,
+    })
+}
+
+/// BER-TLV adat objektumok (egybyte-os tag), a kétbyte-os hosszt (81/82) is kezelve
+fn parse_data_objects(data: &[u8]) -> Result<Vec<(u8, &[u8])>> {
+    let invalid = || NfcError::invalid_response(format!("Érvénytelen adat objektum: {:02X?}", data));
+    let mut objects = Vec::new();
+    let mut i = 0;
+    while i < data.len() {
+        let tag = data[i];
+        let (length, header) = match *data.get(i + 1).ok_or_else(invalid)? {
+            0x81 => (*data.get(i + 2).ok_or_else(invalid)? as usize, 3),
+            0x82 => {
+                let hi = *data.get(i + 2).ok_or_else(invalid)? as usize;
+                let lo = *data.get(i + 3).ok_or_else(invalid)? as usize;
+                ((hi << 8) | lo, 4)
+            }
+            n => (n as usize, 2),
+        };
+        let value = data.get(i + header..i + header + length).ok_or_else(invalid)?;
+        objects.push((tag, value));
+        i += header + length;
+    }
+    Ok(objects)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_short_and_long_length_objects() {
+        let long_value = vec![0xAA; 0x90];
+        let mut data = vec![0xC0, 0x03, 0x00, 0x90, 0x00, 0x97, 0x81, 0x90];
+        data.extend_from_slice(&long_value);
+        data.extend_from_slice(&[0x96, 0x82, 0x00, 0x02, 0x01, 0x02]);
+
+        let objects = parse_data_objects(&data).unwrap();
+        assert_eq!(objects.len(), 3);
+        assert_eq!(objects[0], (0xC0, &[0x00, 0x90, 0x00][..]));
+        assert_eq!(objects[1], (0x97, &long_value[..]));
+        assert_eq!(objects[2], (0x96, &[0x01, 0x02][..]));
+    }
+
+    #[test]
+    fn rejects_truncated_objects() {
+        assert!(parse_data_objects(&[0xC0]).is_err());
+        assert!(parse_data_objects(&[0xC0, 0x03, 0x00]).is_err());
+        assert!(parse_data_objects(&[0x97, 0x81]).is_err());
+        assert!(parse_data_objects(&[0x97, 0x82, 0x00]).is_err());
+        assert!(parse_data_objects(&[]).unwrap().is_empty());
+    }
+}
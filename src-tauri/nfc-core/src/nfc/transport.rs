@@ -0,0 +1,286 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use tracing::debug;
+
+use super::error::NfcError;
+use super::status_word;
+
+/// Kommunikációs hibák osztályozása
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransmitErrorKind {
+    /// Átmeneti RF hiba (gyenge csatolás, timeout): újrapróbálható
+    TransientRf,
+    /// A címkét levették az olvasóról (vagy az olvasót leválasztották)
+    CardRemoved,
+    /// A címke elutasította a műveletet password védelem miatt
+    AuthFailure,
+    /// A lap zárolva van (lock bitek), az írás nem lehetséges
+    LockedPage,
+    /// Protokoll / egyéb hiba (érvénytelen parancs, nem támogatott művelet)
+    Protocol,
+}
+
+impl TransmitErrorKind {
+    /// Van-e értelme újrapróbálni ugyanazt a parancsot
+    pub fn is_retryable(self) -> bool {
+        self == TransmitErrorKind::TransientRf
+    }
+}
+
+/// Osztályozott kommunikációs hiba
+#[derive(Debug, Clone)]
+pub struct TransmitError {
+    pub kind: TransmitErrorKind,
+    pub message: String,
+}
+
+impl TransmitError {
+    pub fn new(kind: TransmitErrorKind, message: impl Into<String>) -> Self {
+        TransmitError {
+            kind,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for TransmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({:?})", self.message, self.kind)
+    }
+}
+
+impl std::error::Error for TransmitError {}
+
+/// APDU válasz status word-del
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApduResponse {
+    pub data: Vec<u8>,
+    pub sw1: u8,
+    pub sw2: u8,
+}
+
+impl ApduResponse {
+    /// Nyers válaszból (adat || SW1 SW2)
+    pub fn from_raw(raw: &[u8]) -> Result<Self, TransmitError> {
+        if raw.len() < 2 {
+            return Err(TransmitError::new(
+                TransmitErrorKind::Protocol,
+                "Érvénytelen válasz az NFC címkétől",
+            ));
+        }
+        let (data, sw) = raw.split_at(raw.len() - 2);
+        Ok(ApduResponse {
+            data: data.to_vec(),
+            sw1: sw[0],
+            sw2: sw[1],
+        })
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.sw1 == 0x90 && self.sw2 == 0x00
+    }
+
+    /// Az adat, ha SW=9000, különben osztályozott status word hiba
+    pub fn into_data(self) -> Result<Vec<u8>, NfcError> {
+        if self.is_ok() {
+            return Ok(self.data);
+        }
+        Err(NfcError::status_word(self.sw1, self.sw2))
+    }
+}
+
+/// Status word osztályozása (a sikeres 9000-t a hívó kezeli)
+pub fn classify_status(sw1: u8, sw2: u8) -> TransmitErrorKind {
+    match (sw1, sw2) {
+        // ACR122U / PN533: a címke nem válaszolt időben, vagy RF hiba
+        (0x64, 0x00) | (0x6F, 0x00) | (0x65, 0x81) => TransmitErrorKind::TransientRf,
+        // ACR122U: a PN532 nem válaszolt, vagy ellenőrzőösszeg hiba a válaszában
+        (0x63, 0x01) | (0x63, 0x27) => TransmitErrorKind::TransientRf,
+        // A címke NAK-ot adott (Type 2: password védett lap), hátralévő próbálkozások (63 Cx),
+        // authentication szükséges vagy blokkolva
+        (0x63, 0x00) | (0x69, 0x82) | (0x69, 0x83) => TransmitErrorKind::AuthFailure,
+        (0x63, n) if n & 0xF0 == 0xC0 => TransmitErrorKind::AuthFailure,
+        // A művelet nem engedélyezett a lapon (zárolt)
+        (0x69, 0x81) | (0x69, 0x86) => TransmitErrorKind::LockedPage,
+        _ => TransmitErrorKind::Protocol,
+    }
+}
+
+/// PC/SC hiba osztályozása
+pub fn classify_pcsc(error: pcsc::Error) -> TransmitErrorKind {
+    use pcsc::Error;
+    match error {
+        Error::Timeout
+        | Error::CommError
+        | Error::NotTransacted
+        | Error::ResetCard
+        | Error::UnresponsiveCard
+        | Error::UnpoweredCard => TransmitErrorKind::TransientRf,
+        Error::RemovedCard | Error::NoSmartcard | Error::ReaderUnavailable | Error::UnknownReader => {
+            TransmitErrorKind::CardRemoved
+        }
+        Error::SecurityViolation => TransmitErrorKind::AuthFailure,
+        _ => TransmitErrorKind::Protocol,
+    }
+}
+
+/// libnfc hibakód osztályozása (nfc-types.h: NFC_E*)
+pub fn classify_libnfc(code: i32) -> TransmitErrorKind {
+    match code {
+        // NFC_EIO, NFC_ETIMEOUT, NFC_ERFTRANS
+        -1 | -6 | -20 => TransmitErrorKind::TransientRf,
+        // NFC_ETGRELEASED, NFC_ENOTSUCHDEV
+        -10 | -4 => TransmitErrorKind::CardRemoved,
+        // NFC_EMFCAUTHFAIL
+        -30 => TransmitErrorKind::AuthFailure,
+        _ => TransmitErrorKind::Protocol,
+    }
+}
+
+/// Újrapróbálási szabály exponenciális backoff-fal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Összes próbálkozás (1 = nincs újrapróbálás)
+    pub max_attempts: u32,
+    /// Várakozás az első újrapróbálás előtt (ms)
+    pub initial_backoff_ms: u64,
+    /// Maximális várakozás két próbálkozás között (ms)
+    pub max_backoff_ms: u64,
+}
+
+impl RetryPolicy {
+    pub const DEFAULT: RetryPolicy = RetryPolicy {
+        max_attempts: 3,
+        initial_backoff_ms: 20,
+        max_backoff_ms: 200,
+    };
+
+    /// Egyetlen próbálkozás (pl. PWD_AUTH: minden ismétlés az AUTHLIM-ből fogyhat)
+    pub const NONE: RetryPolicy = RetryPolicy {
+        max_attempts: 1,
+        initial_backoff_ms: 0,
+        max_backoff_ms: 0,
+    };
+
+    fn backoff(&self, retry: u32) -> Duration {
+        let ms = self
+            .initial_backoff_ms
+            .saturating_mul(1u64 << retry.min(16))
+            .min(self.max_backoff_ms);
+        Duration::from_millis(ms)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::DEFAULT
+    }
+}
+
+static RETRY_POLICY: Mutex<RetryPolicy> = Mutex::new(RetryPolicy::DEFAULT);
+
+/// Az aktuális (globális) újrapróbálási szabály
+pub fn retry_policy() -> RetryPolicy {
+    *RETRY_POLICY.lock().unwrap()
+}
+
+pub fn set_retry_policy(policy: RetryPolicy) {
+    *RETRY_POLICY.lock().unwrap() = RetryPolicy {
+        max_attempts: policy.max_attempts.max(1),
+        ..policy
+    };
+}
+
+/// Nyers APDU küldés (a válasz a status word-del együtt)
+pub trait RawTransmit {
+    fn transmit_raw(&self, apdu: &[u8]) -> Result<Vec<u8>, TransmitError>;
+}
+
+impl RawTransmit for pcsc::Card {
+    fn transmit_raw(&self, apdu: &[u8]) -> Result<Vec<u8>, TransmitError> {
+        let mut response_buffer = [0u8; 256];
+        self.transmit(apdu, &mut response_buffer)
+            .map(|response| response.to_vec())
+            .map_err(|e| {
+                TransmitError::new(
+                    classify_pcsc(e),
+                    format!("Nem sikerült kommunikálni az NFC címkével: {}", e),
+                )
+            })
+    }
+}
+
+/// APDU küldés újrapróbálással: átmeneti hibánál (kommunikációs hiba vagy RF hibát jelző
+/// status word) a szabály szerint várakozik és újraküldi. A status word ellenőrzése a hívóé.
+pub fn transmit_with_retry<T: RawTransmit + ?Sized>(
+    card: &T,
+    apdu: &[u8],
+    policy: &RetryPolicy,
+) -> Result<ApduResponse, TransmitError> {
+    let mut attempt = 1;
+    loop {
+        let result = card
+            .transmit_raw(apdu)
+            .and_then(|raw| ApduResponse::from_raw(&raw));
+
+        let retry_reason = match &result {
+            Ok(response) if !response.is_ok() && classify_status(response.sw1, response.sw2).is_retryable() => {
+                format!(
+                    "{}, SW1=0x{:02X}, SW2=0x{:02X}",
+                    status_word::describe(response.sw1, response.sw2),
+                    response.sw1,
+                    response.sw2
+                )
+            }
+            Err(e) if e.kind.is_retryable() => e.message.clone(),
+            _ => return result,
+        };
+
+        if attempt >= policy.max_attempts {
+            return result;
+        }
+        let backoff = policy.backoff(attempt - 1);
+        debug!(
+            "Átmeneti hiba ({}), újrapróbálás {} ms múlva ({}/{})",
+            retry_reason,
+            backoff.as_millis(),
+            attempt + 1,
+            policy.max_attempts
+        );
+        thread::sleep(backoff);
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auth_status_words_are_auth_failures() {
+        for (sw1, sw2) in [(0x63, 0x00), (0x63, 0xC2), (0x63, 0xC0), (0x69, 0x82), (0x69, 0x83)] {
+            assert_eq!(classify_status(sw1, sw2), TransmitErrorKind::AuthFailure, "{:02X} {:02X}", sw1, sw2);
+        }
+    }
+
+    #[test]
+    fn pn532_faults_are_not_auth_failures() {
+        assert_eq!(classify_status(0x63, 0x01), TransmitErrorKind::TransientRf);
+        assert_eq!(classify_status(0x63, 0x27), TransmitErrorKind::TransientRf);
+        assert_eq!(classify_status(0x63, 0x7F), TransmitErrorKind::Protocol);
+        assert!(classify_status(0x63, 0x01).is_retryable());
+    }
+
+    #[test]
+    fn rf_and_lock_status_words() {
+        assert_eq!(classify_status(0x64, 0x00), TransmitErrorKind::TransientRf);
+        assert_eq!(classify_status(0x65, 0x81), TransmitErrorKind::TransientRf);
+        assert_eq!(classify_status(0x69, 0x81), TransmitErrorKind::LockedPage);
+        assert_eq!(classify_status(0x69, 0x86), TransmitErrorKind::LockedPage);
+        assert_eq!(classify_status(0x6A, 0x82), TransmitErrorKind::Protocol);
+    }
+}
@@ -0,0 +1,95 @@
+use serde::Serialize;
+
+use super::error::{NfcError, Result};
+
+/// Egy írási művelet által a címkére küldött lapok (a visszaolvasáshoz)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WrittenPages {
+    pub start_page: u8,
+    /// A kiírt byte-ok, lapokra (4 byte) kiegészítve
+    pub data: Vec<u8>,
+}
+
+impl WrittenPages {
+    pub fn new(start_page: u8) -> Self {
+        WrittenPages {
+            start_page,
+            data: Vec::new(),
+        }
+    }
+
+    /// Egy lap rögzítése; a lapoknak folytonosnak kell lenniük
+    pub fn push(&mut self, page: &[u8; 4]) {
+        self.data.extend_from_slice(page);
+    }
+
+    pub fn page_count(&self) -> usize {
+        self.data.len() / 4
+    }
+
+    /// (lap szám, várt tartalom) párok
+    pub fn pages(&self) -> impl Iterator<Item = (u8, [u8; 4])> + '_ {
+        self.data.chunks_exact(4).enumerate().map(move |(i, chunk)| {
+            (
+                self.start_page + i as u8,
+                [chunk[0], chunk[1], chunk[2], chunk[3]],
+            )
+        })
+    }
+}
+
+/// Egy eltérő lap
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PageMismatch {
+    pub page: u8,
+    pub expected: [u8; 4],
+    pub actual: [u8; 4],
+    /// Az eltérő byte-ok indexe a lapon belül (0-3)
+    pub differing_bytes: Vec<u8>,
+}
+
+/// Visszaolvasás eredménye: lapról lapra, byte-ról byte-ra összehasonlítva
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VerifyReport {
+    pub start_page: u8,
+    pub pages_checked: usize,
+    pub mismatches: Vec<PageMismatch>,
+}
+
+impl VerifyReport {
+    /// Összehasonlítás: `read_back` a visszaolvasott lapok (ugyanabban a sorrendben)
+    pub fn compare(written: &WrittenPages, read_back: &[[u8; 4]]) -> Self {
+        let mismatches = written
+            .pages()
+            .zip(read_back)
+            .filter(|((_, expected), actual)| expected != *actual)
+            .map(|((page, expected), actual)| PageMismatch {
+                page,
+                expected,
+                actual: *actual,
+                differing_bytes: (0..4u8)
+                    .filter(|&i| expected[i as usize] != actual[i as usize])
+                    .collect(),
+            })
+            .collect();
+
+        VerifyReport {
+            start_page: written.start_page,
+            pages_checked: read_back.len().min(written.page_count()),
+            mismatches,
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+
+    /// Hiba, ha bármelyik lap eltér (a hiba a teljes riportot tartalmazza)
+    pub fn ensure_ok(self) -> Result<Self> {
+        if self.is_ok() {
+            Ok(self)
+        } else {
+            Err(NfcError::VerificationFailed { report: self })
+        }
+    }
+}
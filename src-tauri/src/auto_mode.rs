@@ -0,0 +1,225 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Mutex;
+
+use crate::nfc::{LockPlan, Secret};
+
+/// Automatikusan végrehajtott feladat minden újonnan felhelyezett címkén
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AutoJob {
+    WriteUrl {
+        url: String,
+    },
+    WriteText {
+        text: String,
+        language: String,
+    },
+    WriteVcard {
+        vcard: String,
+    },
+    /// Password beállítás (kulcs profil esetén a címkénkénti diverzifikált kulcs kerül fel)
+    SetPassword {
+        password: Vec<u8>,
+        pack: Vec<u8>,
+        auth_limit: u8,
+    },
+    /// Teljes címke read-only (VISSZAFORDÍTHATATLAN!)
+    SetReadOnly,
+    /// Lapok zárolása lock bitekkel (VISSZAFORDÍTHATATLAN!)
+    Lock {
+        ranges: Vec<(u8, u8)>,
+        lock_config: bool,
+        cc_read_only: bool,
+        freeze_lock_bits: bool,
+    },
+}
+
+/// A logba kerülő alak: a password rejtve
+impl fmt::Debug for AutoJob {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AutoJob::WriteUrl { url } => f.debug_struct("WriteUrl").field("url", url).finish(),
+            AutoJob::WriteText { text, language } => f
+                .debug_struct("WriteText")
+                .field("text", text)
+                .field("language", language)
+                .finish(),
+            AutoJob::WriteVcard { vcard } => f.debug_struct("WriteVcard").field("vcard", vcard).finish(),
+            AutoJob::SetPassword { password, pack, auth_limit } => f
+                .debug_struct("SetPassword")
+                .field("password", &Secret(password))
+                .field("pack", pack)
+                .field("auth_limit", auth_limit)
+                .finish(),
+            AutoJob::SetReadOnly => f.write_str("SetReadOnly"),
+            AutoJob::Lock { ranges, lock_config, cc_read_only, freeze_lock_bits } => f
+                .debug_struct("Lock")
+                .field("ranges", ranges)
+                .field("lock_config", lock_config)
+                .field("cc_read_only", cc_read_only)
+                .field("freeze_lock_bits", freeze_lock_bits)
+                .finish(),
+        }
+    }
+}
+
+impl AutoJob {
+    /// Visszafordíthatatlan-e a feladat (indítás előtt megerősítést igényel)
+    pub fn is_irreversible(&self) -> bool {
+        matches!(self, AutoJob::SetReadOnly | AutoJob::Lock { .. })
+    }
+}
+
+/// Futó összesítés
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct AutoTally {
+    pub succeeded: u32,
+    pub failed: u32,
+    pub skipped: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoOutcome {
+    Succeeded,
+    Failed,
+    /// Ezt a UID-t ebben a futásban már sikeresen feldolgoztuk
+    Skipped,
+}
+
+/// Egy címke eredménye (esemény a frontendnek)
+#[derive(Debug, Clone, Serialize)]
+pub struct AutoTagResult {
+    pub uid: String,
+    pub outcome: AutoOutcome,
+    pub message: String,
+    pub tally: AutoTally,
+}
+
+/// Az auto mód állapota a frontendnek
+#[derive(Debug, Clone, Serialize)]
+pub struct AutoStatus {
+    pub job: AutoJob,
+    pub tally: AutoTally,
+    pub processed: usize,
+    /// Zároló futásnál: a megerősített címke korlát és az eddigi zárolási kísérletek
+    pub lock_limit: Option<u32>,
+    pub lock_attempts: u32,
+}
+
+/// Zároló futás: csak a megerősített terv kerülhet a címkékre, legfeljebb `max_tags` címkén
+#[derive(Debug, Clone)]
+pub struct ConfirmedLock {
+    pub plan: LockPlan,
+    pub max_tags: u32,
+}
+
+struct AutoRun {
+    job: AutoJob,
+    processed: HashSet<String>,
+    tally: AutoTally,
+    lock: Option<ConfirmedLock>,
+    /// Megkezdett zárolások (a sikertelen is számít: részben már íródhatott)
+    lock_attempts: u32,
+}
+
+/// Auto mód (gyártási futás): a beállított feladat minden új címkén lefut
+///
+/// A sikeresen feldolgozott UID-kat megjegyezzük, így ugyanaz a címke újra
+/// felhelyezve nem kerül újra feldolgozásra. A sikertelen címke újra próbálható.
+#[derive(Default)]
+pub struct AutoMode {
+    run: Mutex<Option<AutoRun>>,
+}
+
+impl AutoMode {
+    /// Indítás; visszafordíthatatlan feladathoz a megerősített zárolás kötelező
+    pub fn start(&self, job: AutoJob, lock: Option<ConfirmedLock>) {
+        *self.run.lock().unwrap() = Some(AutoRun {
+            job,
+            processed: HashSet::new(),
+            tally: AutoTally::default(),
+            lock,
+            lock_attempts: 0,
+        });
+    }
+
+    /// Leállítás; visszaadja a végső összesítést (None, ha nem futott)
+    pub fn stop(&self) -> Option<AutoTally> {
+        self.run.lock().unwrap().take().map(|run| run.tally)
+    }
+
+    pub fn status(&self) -> Option<AutoStatus> {
+        self.run.lock().unwrap().as_ref().map(|run| AutoStatus {
+            job: run.job.clone(),
+            tally: run.tally,
+            processed: run.processed.len(),
+            lock_limit: run.lock.as_ref().map(|lock| lock.max_tags),
+            lock_attempts: run.lock_attempts,
+        })
+    }
+
+    /// Új címke: a végrehajtandó feladat (zárolásnál a megerősített tervvel), vagy
+    /// Err(eredmény), ha a UID-t ki kell hagyni. Ok(None): az auto mód nem fut.
+    pub fn begin(&self, uid: &str) -> Result<Option<(AutoJob, Option<LockPlan>)>, AutoTagResult> {
+        let mut guard = self.run.lock().unwrap();
+        let Some(run) = guard.as_mut() else {
+            return Ok(None);
+        };
+
+        if run.processed.contains(uid) {
+            run.tally.skipped += 1;
+            return Err(AutoTagResult {
+                uid: uid.to_string(),
+                outcome: AutoOutcome::Skipped,
+                message: "Ez a címke már fel van dolgozva, kihagyva".to_string(),
+                tally: run.tally,
+            });
+        }
+
+        if let Some(lock) = &run.lock {
+            if run.lock_attempts >= lock.max_tags {
+                run.tally.skipped += 1;
+                return Err(AutoTagResult {
+                    uid: uid.to_string(),
+                    outcome: AutoOutcome::Skipped,
+                    message: format!(
+                        "Elérte a megerősített címke korlátot ({}), nem zárolva. Indítsd újra az auto módot!",
+                        lock.max_tags
+                    ),
+                    tally: run.tally,
+                });
+            }
+            run.lock_attempts += 1;
+        }
+
+        Ok(Some((run.job.clone(), run.lock.as_ref().map(|lock| lock.plan.clone()))))
+    }
+
+    /// A feladat eredményének rögzítése. None, ha közben leállították az auto módot.
+    pub fn finish(&self, uid: &str, result: Result<String, String>) -> Option<AutoTagResult> {
+        let mut guard = self.run.lock().unwrap();
+        let run = guard.as_mut()?;
+
+        let (outcome, message) = match result {
+            Ok(message) => {
+                run.processed.insert(uid.to_string());
+                run.tally.succeeded += 1;
+                (AutoOutcome::Succeeded, message)
+            }
+            Err(message) => {
+                run.tally.failed += 1;
+                (AutoOutcome::Failed, message)
+            }
+        };
+
+        Some(AutoTagResult {
+            uid: uid.to_string(),
+            outcome,
+            message,
+            tally: run.tally,
+        })
+    }
+}
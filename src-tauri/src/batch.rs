@@ -0,0 +1,536 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::{info, warn};
+
+use crate::auto_mode::AutoOutcome;
+use crate::vault::write_atomic;
+
+/// Egy rekord (oszlopnév -> érték)
+pub type Record = BTreeMap<String, String>;
+
+/// A rekord lista formátuma
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordFormat {
+    /// Fejléc sorral, vesszővel elválasztva, idézőjeles mezőkkel
+    Csv,
+    /// Objektumok tömbje
+    Json,
+}
+
+/// A címkére írt NDEF rekord típusa
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BatchPayload {
+    Url,
+    Text { language: String },
+    Vcard,
+}
+
+/// Batch indítási kérés a frontendtől
+#[derive(Debug, Clone, Deserialize)]
+pub struct BatchRequest {
+    /// CSV vagy JSON szöveg
+    pub records: String,
+    pub format: RecordFormat,
+    pub payload: BatchPayload,
+    pub template: String,
+    /// Az első sor sorszáma ({serial}), alapértelmezés: 1
+    pub serial_start: Option<u64>,
+    /// Manifest fájl; alapértelmezés: az adatkönyvtár manifests/batch-<idő>.json fájlja
+    pub manifest_path: Option<String>,
+}
+
+/// Batch feladat leírása
+#[derive(Debug, Clone)]
+pub struct BatchSpec {
+    pub payload: BatchPayload,
+    /// Sablon: {uid}, {serial}, {row.mező}; literális kapcsos zárójel: {{ és }}
+    pub template: String,
+    pub serial_start: u64,
+    pub records: Vec<Record>,
+}
+
+impl BatchSpec {
+    /// A sor sorszáma ({serial}); None, ha túlcsordulna
+    fn serial(&self, row: usize) -> Option<u64> {
+        self.serial_start.checked_add(row as u64)
+    }
+}
+
+/// Egy címkére írandó tartalom
+#[derive(Debug, Clone)]
+pub struct BatchTask {
+    pub row: usize,
+    pub serial: u64,
+    pub payload: BatchPayload,
+    pub content: String,
+}
+
+/// Mi történjen az új címkével
+#[derive(Debug)]
+pub enum BatchStep {
+    /// Nem fut batch
+    Idle,
+    /// A következő sor megírása
+    Write(BatchTask),
+    /// A címke kimarad (már megírt UID vagy sablon hiba)
+    Skip(BatchTagResult),
+}
+
+/// Egy címke eredménye (esemény a frontendnek)
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchTagResult {
+    pub uid: String,
+    pub row: Option<usize>,
+    pub serial: Option<u64>,
+    pub outcome: AutoOutcome,
+    pub message: String,
+    pub summary: BatchSummary,
+}
+
+/// Batch állapot összesítés
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchSummary {
+    pub total: usize,
+    pub written: usize,
+    pub failed: u32,
+    pub skipped: u32,
+    pub done: bool,
+    pub manifest: String,
+}
+
+/// Manifest bejegyzés: UID <-> sor <-> eredmény
+#[derive(Debug, Clone, Serialize)]
+struct ManifestEntry {
+    row: usize,
+    serial: u64,
+    uid: String,
+    success: bool,
+    content: String,
+    message: String,
+}
+
+#[derive(Serialize)]
+struct Manifest<'a> {
+    payload: &'a BatchPayload,
+    template: &'a str,
+    total_rows: usize,
+    entries: &'a [ManifestEntry],
+}
+
+struct BatchRun {
+    spec: BatchSpec,
+    manifest_path: PathBuf,
+    next_row: usize,
+    processed: HashSet<String>,
+    entries: Vec<ManifestEntry>,
+    failed: u32,
+    skipped: u32,
+}
+
+impl BatchRun {
+    fn summary(&self) -> BatchSummary {
+        BatchSummary {
+            total: self.spec.records.len(),
+            written: self.next_row,
+            failed: self.failed,
+            skipped: self.skipped,
+            done: self.next_row >= self.spec.records.len(),
+            manifest: self.manifest_path.display().to_string(),
+        }
+    }
+
+    fn save_manifest(&self) -> Result<()> {
+        let manifest = Manifest {
+            payload: &self.spec.payload,
+            template: &self.spec.template,
+            total_rows: self.spec.records.len(),
+            entries: &self.entries,
+        };
+        if let Some(parent) = self.manifest_path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Nem sikerült létrehozni a könyvtárat: {}", parent.display()))?;
+        }
+        write_atomic(&self.manifest_path, &serde_json::to_vec_pretty(&manifest)?)
+            .with_context(|| format!("Nem sikerült írni a manifestet: {}", self.manifest_path.display()))?;
+        Ok(())
+    }
+}
+
+/// Batch kódolás: az N. sikeresen feldolgozott címkére az N. rekord kerül
+///
+/// Sikertelen írás után a sor nem lép tovább, a következő címke ugyanazt a sort kapja.
+/// A már megírt UID-k újra felhelyezve kimaradnak. A manifest minden címke után frissül.
+#[derive(Default)]
+pub struct BatchMode {
+    run: Mutex<Option<BatchRun>>,
+}
+
+impl BatchMode {
+    /// Batch indítása. A sablont minden sorra előre kiértékeljük, hogy a hibás
+    /// oszlopnév már indításkor kiderüljön, ne a gyártás közepén.
+    pub fn start(&self, spec: BatchSpec, manifest_path: &Path) -> Result<BatchSummary> {
+        if spec.records.is_empty() {
+            anyhow::bail!("Nincs egyetlen rekord sem");
+        }
+        for (index, record) in spec.records.iter().enumerate() {
+            let serial = spec.serial(index).ok_or_else(|| {
+                anyhow::anyhow!("A(z) {}. sor sorszáma túlcsordul (kezdő sorszám: {})", index + 1, spec.serial_start)
+            })?;
+            render(&spec.template, "00000000000000", serial, record)
+                .with_context(|| format!("Hibás sablon a(z) {}. sorhoz", index + 1))?;
+        }
+
+        let run = BatchRun {
+            spec,
+            manifest_path: manifest_path.to_path_buf(),
+            next_row: 0,
+            processed: HashSet::new(),
+            entries: Vec::new(),
+            failed: 0,
+            skipped: 0,
+        };
+        run.save_manifest()?;
+        let summary = run.summary();
+        *self.run.lock().unwrap() = Some(run);
+        Ok(summary)
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.run.lock().unwrap().is_some()
+    }
+
+    pub fn status(&self) -> Option<BatchSummary> {
+        self.run.lock().unwrap().as_ref().map(|run| run.summary())
+    }
+
+    /// Leállítás; visszaadja a végső összesítést (None, ha nem futott)
+    pub fn stop(&self) -> Option<BatchSummary> {
+        self.run.lock().unwrap().take().map(|run| run.summary())
+    }
+
+    /// Új címke: a következő sor tartalma, vagy a kihagyás eredménye
+    pub fn begin(&self, uid: &str) -> BatchStep {
+        let mut guard = self.run.lock().unwrap();
+        let Some(run) = guard.as_mut() else {
+            return BatchStep::Idle;
+        };
+
+        if run.processed.contains(uid) {
+            run.skipped += 1;
+            return BatchStep::Skip(BatchTagResult {
+                uid: uid.to_string(),
+                row: None,
+                serial: None,
+                outcome: AutoOutcome::Skipped,
+                message: "Ez a címke már meg van írva, kihagyva".to_string(),
+                summary: run.summary(),
+            });
+        }
+
+        let row = run.next_row;
+        let serial = run.spec.serial(row).expect("a sorszámokat indításkor ellenőriztük");
+        let content = render(&run.spec.template, uid, serial, &run.spec.records[row]);
+        match content {
+            Ok(content) => BatchStep::Write(BatchTask {
+                row,
+                serial,
+                payload: run.spec.payload.clone(),
+                content,
+            }),
+            Err(e) => {
+                run.failed += 1;
+                BatchStep::Skip(BatchTagResult {
+                    uid: uid.to_string(),
+                    row: Some(row),
+                    serial: Some(serial),
+                    outcome: AutoOutcome::Failed,
+                    message: format!("Sablon hiba: {}", e),
+                    summary: run.summary(),
+                })
+            }
+        }
+    }
+
+    /// Az írás eredményének rögzítése a manifestben. Az utolsó sor után a batch véget ér.
+    /// None, ha közben leállították a batch-et.
+    pub fn finish(&self, uid: &str, task: BatchTask, result: Result<String, String>) -> Option<BatchTagResult> {
+        let mut guard = self.run.lock().unwrap();
+        let run = guard.as_mut()?;
+
+        let success = result.is_ok();
+        let mut message = match result {
+            Ok(message) | Err(message) => message,
+        };
+        if success {
+            run.processed.insert(uid.to_string());
+            run.next_row += 1;
+        } else {
+            run.failed += 1;
+        }
+        run.entries.push(ManifestEntry {
+            row: task.row,
+            serial: task.serial,
+            uid: uid.to_string(),
+            success,
+            content: task.content,
+            message: message.clone(),
+        });
+        if let Err(e) = run.save_manifest() {
+            warn!("Manifest mentési hiba: {}", e);
+            message = format!("{}\n⚠️ Manifest mentési hiba: {}", message, e);
+        }
+
+        let summary = run.summary();
+        if summary.done {
+            info!("Batch kész: {} címke, manifest: {}", summary.written, summary.manifest);
+            *guard = None;
+        }
+
+        Some(BatchTagResult {
+            uid: uid.to_string(),
+            row: Some(task.row),
+            serial: Some(task.serial),
+            outcome: if success { AutoOutcome::Succeeded } else { AutoOutcome::Failed },
+            message,
+            summary,
+        })
+    }
+}
+
+/// Sablon kiértékelése: {uid}, {serial}, {row.mező}; {{ és }} literális zárójel
+pub fn render(template: &str, uid: &str, serial: u64, record: &Record) -> Result<String> {
+    let mut output = String::with_capacity(template.len());
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                output.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                output.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => anyhow::bail!("Lezáratlan helyettesítő: {{{}", name),
+                    }
+                }
+                let name = name.trim();
+                match name {
+                    "uid" => output.push_str(uid),
+                    "serial" => output.push_str(&serial.to_string()),
+                    _ => {
+                        let field = name
+                            .strip_prefix("row.")
+                            .ok_or_else(|| anyhow::anyhow!("Ismeretlen helyettesítő: {{{}}}", name))?;
+                        let value = record
+                            .get(field)
+                            .ok_or_else(|| anyhow::anyhow!("Nincs ilyen oszlop: {}", field))?;
+                        output.push_str(value);
+                    }
+                }
+            }
+            '}' => anyhow::bail!("Páratlan '}}' a sablonban (literálisan: }}}})"),
+            c => output.push(c),
+        }
+    }
+
+    Ok(output)
+}
+
+/// Rekordok beolvasása CSV vagy JSON szövegből
+pub fn parse_records(input: &str, format: RecordFormat) -> Result<Vec<Record>> {
+    let input = input.trim_start_matches('\u{feff}');
+    match format {
+        RecordFormat::Json => {
+            let rows: Vec<serde_json::Map<String, serde_json::Value>> = serde_json::from_str(input)
+                .context("A JSON-nak objektumok tömbjének kell lennie")?;
+            Ok(rows
+                .into_iter()
+                .map(|row| {
+                    row.into_iter()
+                        .map(|(key, value)| {
+                            let value = match value {
+                                serde_json::Value::String(s) => s,
+                                serde_json::Value::Null => String::new(),
+                                other => other.to_string(),
+                            };
+                            (key, value)
+                        })
+                        .collect()
+                })
+                .collect())
+        }
+        RecordFormat::Csv => {
+            let mut rows = parse_csv(input)?.into_iter();
+            let header = rows
+                .next()
+                .ok_or_else(|| anyhow::anyhow!("Üres CSV (hiányzik a fejléc sor)"))?;
+            let header: Vec<String> = header.into_iter().map(|h| h.trim().to_string()).collect();
+
+            let mut records = Vec::new();
+            for (index, row) in rows.enumerate() {
+                if row.len() == 1 && row[0].is_empty() {
+                    continue; // üres sor
+                }
+                if row.len() != header.len() {
+                    anyhow::bail!(
+                        "A(z) {}. adatsorban {} mező van, a fejlécben {}",
+                        index + 1,
+                        row.len(),
+                        header.len()
+                    );
+                }
+                records.push(header.iter().cloned().zip(row).collect());
+            }
+            Ok(records)
+        }
+    }
+}
+
+/// Egyszerű RFC 4180 CSV feldolgozás (idézőjeles mezők, "" escape, CRLF)
+fn parse_csv(input: &str) -> Result<Vec<Vec<String>>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                c => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => row.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            c => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        anyhow::bail!("Lezáratlan idézőjel a CSV-ben");
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(fields: &[(&str, &str)]) -> Record {
+        fields.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    fn spec(serial_start: u64, records: Vec<Record>) -> BatchSpec {
+        BatchSpec {
+            payload: BatchPayload::Url,
+            template: "https://example.com/{serial}".to_string(),
+            serial_start,
+            records,
+        }
+    }
+
+    #[test]
+    fn render_substitutes_placeholders() {
+        let row = record(&[("sku", "A-1")]);
+        let output = render("https://x.hu/{uid}/{ serial }?sku={row.sku}", "04A1B2", 42, &row).unwrap();
+        assert_eq!(output, "https://x.hu/04A1B2/42?sku=A-1");
+    }
+
+    #[test]
+    fn render_escapes_braces() {
+        let output = render("{{\"id\": {serial}}}", "", 7, &Record::new()).unwrap();
+        assert_eq!(output, "{\"id\": 7}");
+    }
+
+    #[test]
+    fn render_rejects_bad_templates() {
+        let row = record(&[("sku", "A-1")]);
+        assert!(render("{row.missing}", "", 1, &row).is_err());
+        assert!(render("{unknown}", "", 1, &row).is_err());
+        assert!(render("{serial", "", 1, &row).is_err());
+        assert!(render("serial}", "", 1, &row).is_err());
+    }
+
+    #[test]
+    fn csv_quotes_escapes_and_crlf() {
+        let rows = parse_csv("name,note\r\n\"Kovács, Anna\",\"azt mondta: \"\"szia\"\"\"\r\nBéla,\"két\nsor\"").unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                vec!["name".to_string(), "note".to_string()],
+                vec!["Kovács, Anna".to_string(), "azt mondta: \"szia\"".to_string()],
+                vec!["Béla".to_string(), "két\nsor".to_string()],
+            ]
+        );
+        assert!(parse_csv("a,\"b").is_err());
+    }
+
+    #[test]
+    fn csv_records_use_header_and_skip_empty_rows() {
+        let records = parse_records("\u{feff}sku , name\n1,Alma\n\n2,Körte\n", RecordFormat::Csv).unwrap();
+        assert_eq!(records, vec![record(&[("sku", "1"), ("name", "Alma")]), record(&[("sku", "2"), ("name", "Körte")])]);
+        assert!(parse_records("sku,name\n1\n", RecordFormat::Csv).is_err());
+    }
+
+    #[test]
+    fn json_records_stringify_values() {
+        let records = parse_records(r#"[{"sku": 1, "name": "Alma", "note": null}]"#, RecordFormat::Json).unwrap();
+        assert_eq!(records, vec![record(&[("sku", "1"), ("name", "Alma"), ("note", "")])]);
+    }
+
+    #[test]
+    fn start_rejects_overflowing_serials() {
+        let path = std::env::temp_dir()
+            .join(format!("nfc-batch-overflow-{}", std::process::id()))
+            .join("manifest.json");
+        let batch = BatchMode::default();
+        let records = vec![Record::new(), Record::new()];
+        assert!(batch.start(spec(u64::MAX, records), &path).is_err());
+        assert!(!batch.is_running());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn manifest_is_written_on_start() {
+        let dir = std::env::temp_dir().join(format!("nfc-batch-start-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("manifest.json");
+        let batch = BatchMode::default();
+        let summary = batch.start(spec(u64::MAX, vec![Record::new()]), &path).unwrap();
+        assert_eq!(summary.total, 1);
+        assert!(path.exists());
+        assert!(!dir.join("manifest.json.tmp").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,168 @@
+use anyhow::Result;
+use rand::RngCore;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::nfc::LockPlan;
+
+/// Ennyi ideje van a felhasználónak megerősíteni a visszafordíthatatlan műveletet
+const CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Kétlépcsős megerősítés visszafordíthatatlan műveletekhez (read-only, lock bitek, CFGLCK)
+///
+/// Az első hívás csak egy tokent ad vissza, ami a címke UID-jához (auto módban a futás
+/// címke korlátjához) és a tervezett lap módosításokhoz kötött. A művelet csak akkor fut le,
+/// ha a második hívás ugyanarra a címkére, ugyanazzal a tervvel és a határidőn belül érkezik.
+#[derive(Default)]
+pub struct ConfirmationGuard {
+    pending: Mutex<HashMap<String, PendingConfirmation>>,
+}
+
+/// Amire a megerősítés szól
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Subject {
+    /// Egyetlen címke (UID)
+    Tag(Vec<u8>),
+    /// Auto mód futás legfeljebb ennyi címkével
+    Run { max_tags: u32 },
+}
+
+struct PendingConfirmation {
+    operation: &'static str,
+    subject: Subject,
+    plan: LockPlan,
+    issued_at: Instant,
+}
+
+/// Megerősítésre váró művelet adatai a frontendnek
+#[derive(Debug, Clone, Serialize)]
+pub struct Challenge {
+    pub token: String,
+    pub expires_in_secs: u64,
+}
+
+impl ConfirmationGuard {
+    /// Új token kiadása a művelethez
+    pub fn issue(&self, operation: &'static str, subject: Subject, plan: &LockPlan) -> Challenge {
+        let mut bytes = [0u8; 16];
+        rand::rngs::OsRng.fill_bytes(&mut bytes);
+        let token: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+
+        let mut pending = self.pending.lock().unwrap();
+        pending.retain(|_, p| p.issued_at.elapsed() < CONFIRMATION_TIMEOUT);
+        pending.insert(
+            token.clone(),
+            PendingConfirmation {
+                operation,
+                subject,
+                plan: plan.clone(),
+                issued_at: Instant::now(),
+            },
+        );
+
+        Challenge {
+            token,
+            expires_in_secs: CONFIRMATION_TIMEOUT.as_secs(),
+        }
+    }
+
+    /// Token beváltása. A token egyszer használható, sikertelen próbálkozás után is érvénytelen.
+    pub fn redeem(&self, token: &str, operation: &'static str, subject: &Subject, plan: &LockPlan) -> Result<()> {
+        let pending = self
+            .pending
+            .lock()
+            .unwrap()
+            .remove(token)
+            .ok_or_else(|| anyhow::anyhow!("Ismeretlen vagy már felhasznált megerősítő token"))?;
+
+        if pending.issued_at.elapsed() >= CONFIRMATION_TIMEOUT {
+            anyhow::bail!("A megerősítés lejárt ({} mp), kezdd újra", CONFIRMATION_TIMEOUT.as_secs());
+        }
+        if pending.operation != operation {
+            anyhow::bail!("A token egy másik művelethez tartozik ({})", pending.operation);
+        }
+        match (&pending.subject, subject) {
+            (Subject::Tag(confirmed), Subject::Tag(uid)) if confirmed == uid => {}
+            (Subject::Run { max_tags: confirmed }, Subject::Run { max_tags }) if confirmed == max_tags => {}
+            (Subject::Tag(_), _) => {
+                anyhow::bail!("A token egy másik címkéhez tartozik! Helyezd vissza az eredeti címkét.")
+            }
+            (Subject::Run { .. }, _) => anyhow::bail!("A címke korlát megváltozott a megerősítés óta, kezdd újra"),
+        }
+        if pending.plan != *plan {
+            anyhow::bail!("A tervezett lap módosítások megváltoztak a megerősítés óta, kezdd újra");
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nfc::lock::{plan_lock, LockRequest};
+    use crate::nfc::ntag216::NtagChip;
+
+    const UID: [u8; 7] = [0x04, 0xA1, 0xB2, 0xC3, 0xD4, 0xE5, 0x80];
+
+    fn plan(chip: NtagChip) -> LockPlan {
+        plan_lock(chip, &LockRequest::read_only(chip)).unwrap()
+    }
+
+    #[test]
+    fn token_is_single_use() {
+        let guard = ConfirmationGuard::default();
+        let subject = Subject::Tag(UID.to_vec());
+        let challenge = guard.issue("read_only", subject.clone(), &plan(NtagChip::Ntag216));
+        assert_eq!(challenge.expires_in_secs, 60);
+
+        guard.redeem(&challenge.token, "read_only", &subject, &plan(NtagChip::Ntag216)).unwrap();
+        assert!(guard.redeem(&challenge.token, "read_only", &subject, &plan(NtagChip::Ntag216)).is_err());
+    }
+
+    #[test]
+    fn failed_redeem_consumes_the_token() {
+        let guard = ConfirmationGuard::default();
+        let subject = Subject::Tag(UID.to_vec());
+        let challenge = guard.issue("read_only", subject.clone(), &plan(NtagChip::Ntag216));
+
+        let mut other_uid = UID.to_vec();
+        other_uid[6] ^= 0x01;
+        assert!(guard.redeem(&challenge.token, "read_only", &Subject::Tag(other_uid), &plan(NtagChip::Ntag216)).is_err());
+        assert!(guard.redeem(&challenge.token, "read_only", &subject, &plan(NtagChip::Ntag216)).is_err());
+    }
+
+    #[test]
+    fn operation_subject_and_plan_must_match() {
+        let guard = ConfirmationGuard::default();
+        let run = Subject::Run { max_tags: 5 };
+
+        let challenge = guard.issue("auto_lock", run.clone(), &plan(NtagChip::Ntag216));
+        assert!(guard.redeem(&challenge.token, "read_only", &run, &plan(NtagChip::Ntag216)).is_err());
+
+        let challenge = guard.issue("auto_lock", run.clone(), &plan(NtagChip::Ntag216));
+        assert!(guard.redeem(&challenge.token, "auto_lock", &Subject::Run { max_tags: 6 }, &plan(NtagChip::Ntag216)).is_err());
+
+        let challenge = guard.issue("auto_lock", run.clone(), &plan(NtagChip::Ntag216));
+        assert!(guard.redeem(&challenge.token, "auto_lock", &run, &plan(NtagChip::Ntag213)).is_err());
+
+        let challenge = guard.issue("auto_lock", run.clone(), &plan(NtagChip::Ntag216));
+        assert!(guard.redeem(&challenge.token, "auto_lock", &run, &plan(NtagChip::Ntag216)).is_ok());
+    }
+
+    #[test]
+    fn expired_token_is_rejected() {
+        let guard = ConfirmationGuard::default();
+        let subject = Subject::Tag(UID.to_vec());
+        let challenge = guard.issue("read_only", subject.clone(), &plan(NtagChip::Ntag216));
+        guard.pending.lock().unwrap().get_mut(&challenge.token).unwrap().issued_at =
+            Instant::now() - CONFIRMATION_TIMEOUT;
+
+        let error = guard
+            .redeem(&challenge.token, "read_only", &subject, &plan(NtagChip::Ntag216))
+            .unwrap_err();
+        assert!(error.to_string().contains("lejárt"), "{}", error);
+    }
+}
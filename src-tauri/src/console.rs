@@ -0,0 +1,115 @@
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::info;
+
+use crate::nfc::driver::{NativeResponse, ReaderDriver};
+use crate::nfc::{secret, status_word, NfcCard, RetryPolicy};
+
+/// Ennyi parancsot őrzünk meg az előzményekben
+const HISTORY_LIMIT: usize = 200;
+
+/// Szakértői konzol: tetszőleges natív NFC-A parancs (pl. `30 04`, `60`, `3A 00 0F`) küldése
+/// a címkének az olvasó driverén keresztül, a nyers válasszal
+///
+/// A CRC-t az olvasó számolja és ellenőrzi, a válasz CRC nélkül érkezik. Csak szakértői
+/// módban használható (beállítás), mert egy rossz WRITE / lock parancs a címkét tönkreteheti.
+#[derive(Default)]
+pub struct ExpertConsole {
+    enabled: AtomicBool,
+    history: Mutex<VecDeque<ConsoleEntry>>,
+}
+
+/// Egy elküldött parancs és a címke válasza
+#[derive(Debug, Clone, Serialize)]
+pub struct ConsoleEntry {
+    /// Unix idő ezredmásodpercben
+    pub timestamp_ms: u64,
+    pub driver: ReaderDriver,
+    /// A natív parancs (hex)
+    pub command: String,
+    /// Az olvasónak ténylegesen küldött pseudo-APDU (hex)
+    pub apdu: String,
+    pub result: ConsoleResult,
+}
+
+/// A parancs eredménye
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ConsoleResult {
+    /// A címke válasza (hex)
+    Data { data: String },
+    /// 4 bites NAK a címkétől
+    Nak { nak: u8, description: String },
+    /// A címke nem válaszolt
+    NoResponse { reason: String },
+    /// Olvasó / kommunikációs hiba (`NfcError` kód és üzenet)
+    Error { code: &'static str, message: String },
+}
+
+impl ExpertConsole {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::SeqCst)
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Natív parancs küldése. A hibát is az előzményekbe írjuk, ezért a bejegyzést mindig visszaadjuk.
+    ///
+    /// Nincs újrapróbálás: a parancs bármi lehet (WRITE, PWD_AUTH), amit nem szabad megismételni.
+    pub fn transceive(&self, card: &dyn NfcCard, command: &[u8]) -> ConsoleEntry {
+        let driver = ReaderDriver::identify(card);
+        let apdu = driver.framing().wrap(command);
+        info!("Natív parancs ({}): {}", driver.label(), secret::redact_native(command));
+
+        let result = match driver.transmit_native(card, command, &RetryPolicy::NONE) {
+            Ok(NativeResponse::Data(data)) => ConsoleResult::Data { data: to_hex(&data) },
+            Ok(NativeResponse::Nak(nak)) => ConsoleResult::Nak {
+                nak,
+                description: status_word::describe_type2_ack(nak).to_string(),
+            },
+            Ok(NativeResponse::NoResponse { reason }) => ConsoleResult::NoResponse { reason },
+            Err(e) => ConsoleResult::Error {
+                code: e.code(),
+                message: e.to_string(),
+            },
+        };
+        info!("Válasz: {:?}", result);
+
+        let entry = ConsoleEntry {
+            timestamp_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+            driver,
+            command: to_hex(command),
+            apdu: to_hex(&apdu),
+            result,
+        };
+
+        let mut history = self.history.lock().unwrap();
+        if history.len() == HISTORY_LIMIT {
+            history.pop_front();
+        }
+        history.push_back(entry.clone());
+        entry
+    }
+
+    /// Az előzmények, a legrégebbi elöl
+    pub fn history(&self) -> Vec<ConsoleEntry> {
+        self.history.lock().unwrap().iter().cloned().collect()
+    }
+
+    pub fn clear_history(&self) {
+        self.history.lock().unwrap().clear();
+    }
+}
+
+/// Hex formátum szóközzel elválasztva (a konzolba visszamásolható alakban)
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,102 @@
+use anyhow::{Context, Result};
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::EnvFilter;
+
+/// Ennyi log sort őrzünk meg a memóriában (`recent`, hibajelentésekhez)
+const RECENT_LIMIT: usize = 2000;
+/// E fölött a log fájlt induláskor .1 végződéssel félretesszük
+const MAX_LOG_FILE_SIZE: u64 = 5 * 1024 * 1024;
+
+static RECENT: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+static LOG_FILE: Mutex<Option<File>> = Mutex::new(None);
+
+/// Log inicializálás: konzol, memória puffer és (a `set_log_file` után) log fájl
+///
+/// A szint a `NFC_LOG` környezeti változóval állítható (pl. `NFC_LOG=trace`),
+/// alapértelmezés: az alkalmazás és az `nfc_core` könyvtár debug, a többi függőség warn szinten.
+pub fn init() {
+    let filter = EnvFilter::try_from_env("NFC_LOG")
+        .unwrap_or_else(|_| EnvFilter::new(format!("warn,{}=debug,nfc_core=debug", env!("CARGO_CRATE_NAME"))));
+
+    let result = tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer().with_target(false))
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_ansi(false)
+                .with_target(false)
+                .with_writer(SinkWriter::default),
+        )
+        .try_init();
+    if let Err(e) = result {
+        eprintln!("A log inicializálás nem sikerült: {}", e);
+    }
+}
+
+/// A log fájl megnyitása (hozzáfűzés); a túl nagy fájlt előbb félretesszük
+pub fn set_log_file(path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Nem sikerült létrehozni a könyvtárat: {}", parent.display()))?;
+    }
+    if fs::metadata(path).map(|m| m.len() > MAX_LOG_FILE_SIZE).unwrap_or(false) {
+        fs::rename(path, path.with_extension("log.1"))
+            .with_context(|| format!("Nem sikerült a log fájl rotálása: {}", path.display()))?;
+    }
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Nem sikerült megnyitni a log fájlt: {}", path.display()))?;
+    *LOG_FILE.lock().unwrap() = Some(file);
+    Ok(())
+}
+
+/// A legutóbbi log sorok (legfeljebb `limit`), a legrégebbi elöl
+pub fn recent(limit: usize) -> Vec<String> {
+    let recent = RECENT.lock().unwrap();
+    recent.iter().skip(recent.len().saturating_sub(limit)).cloned().collect()
+}
+
+/// Egy log esemény formázott szövege; eldobáskor kerül a memória pufferbe és a log fájlba
+#[derive(Default)]
+struct SinkWriter {
+    buffer: Vec<u8>,
+}
+
+impl Write for SinkWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for SinkWriter {
+    fn drop(&mut self) {
+        if self.buffer.is_empty() {
+            return;
+        }
+        if let Some(file) = LOG_FILE.lock().unwrap().as_mut() {
+            // A log fájl írási hibáját nincs hova jelenteni
+            let _ = file.write_all(&self.buffer);
+        }
+        let text = String::from_utf8_lossy(&self.buffer);
+        let mut recent = RECENT.lock().unwrap();
+        for line in text.lines() {
+            if recent.len() == RECENT_LIMIT {
+                recent.pop_front();
+            }
+            recent.push_back(line.to_string());
+        }
+    }
+}
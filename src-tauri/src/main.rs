@@ -3,10 +3,33 @@
   windows_subsystem = "windows"
 )]
 
-mod nfc;
+mod auto_mode;
+mod batch;
+mod confirmation;
+mod console;
+mod logging;
+mod settings;
+mod vault;
 
-use nfc::{NfcReader, Ntag216};
+use nfc_core::{command_error, nfc};
+
+use auto_mode::{AutoJob, AutoMode, AutoStatus, ConfirmedLock};
+use batch::{BatchMode, BatchPayload, BatchRequest, BatchSpec, BatchStep, BatchSummary, BatchTask};
+use command_error::CommandError;
+use confirmation::{Challenge, ConfirmationGuard, Subject};
+use console::{ConsoleEntry, ExpertConsole};
+use nfc::acr122u::{self, Feedback, PiccParameters};
+use nfc::card_trait::Iso14443aTarget;
+use nfc::driver::ReaderDriver;
+use nfc::ntag216::{NtagChip, NtagVersion};
+use nfc::trace::ReplayCard;
+use nfc::{Backend, CardMonitor, DiversifiedKey, KeyProfile, LockPlan, LockRequest, MonitorEvent, NfcCard, NfcError, NfcSession, Ntag216, ReaderSelector, RetryPolicy, Secret, WrittenPages};
 use serde::{Deserialize, Serialize};
+use settings::Settings;
+use std::sync::Mutex;
+use tauri::Manager;
+use tracing::{debug, info, warn};
+use vault::{Vault, VaultEntry, VaultEntrySummary};
 
 #[derive(Debug, Serialize, Deserialize)]
 struct Response {
@@ -27,436 +50,1330 @@ struct TextConfig {
   language: String,
 }
 
-/// URL írása NTAG216 címkére
-#[tauri::command]
-fn write_url(url: String, password: Option<Vec<u8>>) -> Result<String, String> {
-  println!("📝 write_url CALLED");
-  println!("  📥 URL: {}", url);
-  if let Some(ref pwd) = password {
-    println!("  📥 Password: {:?} (len: {})", pwd, pwd.len());
+/// Tartós olvasó munkamenet: a parancsok ugyanazt a PC/SC kapcsolatot használják
+#[derive(Default)]
+struct SessionState(Mutex<NfcSession>);
+
+/// Háttér címke monitor (az alkalmazás élettartama alatt fut)
+#[derive(Default)]
+struct MonitorState(Mutex<Option<CardMonitor>>);
+
+/// Címke jelenlét a frontendnek
+#[derive(Debug, Serialize)]
+struct CardStatus {
+  present: bool,
+  reader: Option<String>,
+  /// Az olvasóhoz választott driver (natív parancsok keretezése)
+  driver: Option<ReaderDriver>,
+  backend: Backend,
+  /// UID / ATQA / SAK, ha a háttér a kiválasztáskor megadja (libnfc)
+  target: Option<Iso14443aTarget>,
+}
+
+/// Címke információk natív NTAG parancsokkal (GET_VERSION, READ_SIG, READ_CNT)
+#[derive(Debug, Serialize)]
+struct TagInfo {
+  driver: ReaderDriver,
+  version: NtagVersion,
+  /// Az originalitás aláírás hex formában; None, ha az olvasó/címke nem adta vissza
+  signature: Option<String>,
+  /// None, ha az NFC számláló nincs engedélyezve vagy nem olvasható
+  counter: Option<u32>,
+}
+
+/// A kiválasztott mester kulcs profil (kulcs-diverzifikációhoz)
+#[derive(Default)]
+struct KeyProfileState(Mutex<Option<KeyProfile>>);
+
+/// Diverzifikált password és PACK a címke UID-jából, ha van kiválasztott mester kulcs profil.
+/// Ilyenkor a kézzel megadott password-ot figyelmen kívül hagyjuk.
+fn diversified_key(ntag: &Ntag216, card: &dyn NfcCard, profile: &KeyProfileState) -> Result<Option<DiversifiedKey>, CommandError> {
+  let profile = profile.0.lock().unwrap();
+  match profile.as_ref() {
+    Some(profile) => {
+      let uid = ntag.read_uid(card)
+        .map_err(|e| CommandError::from(e).context("UID olvasási hiba"))?;
+      info!("Diverzifikált password a(z) '{}' profilból", profile.name);
+      Ok(Some(profile.derive(&uid)))
+    }
+    None => Ok(None),
+  }
+}
+
+/// A feloldott (unlock-olt) jelszó tároló
+#[derive(Default)]
+struct VaultState(Mutex<Option<Vault>>);
+
+/// Password és PACK feloldása a címkéhez, ebben a sorrendben:
+/// 1. kiválasztott mester kulcs profil (UID-ból származtatva),
+/// 2. kézzel megadott password,
+/// 3. a jelszó tárolóban a címke UID-jához tartozó bejegyzés.
+fn resolve_credentials(
+  ntag: &Ntag216,
+  card: &dyn NfcCard,
+  profile: &KeyProfileState,
+  vault: &VaultState,
+  password: Option<[u8; 4]>,
+  pack: Option<[u8; 2]>,
+) -> Result<(Option<[u8; 4]>, Option<[u8; 2]>), CommandError> {
+  if let Some(key) = diversified_key(ntag, card, profile)? {
+    return Ok((Some(key.password), Some(key.pack)));
+  }
+  if password.is_some() {
+    return Ok((password, pack));
   }
   
-  let reader = NfcReader::new()
-    .map_err(|e| format!("NFC olvasó inicializálási hiba: {}", e))?;
-  
-  let card = reader.connect()
-    .map_err(|e| format!("Csatlakozási hiba: {}", e))?;
+  let vault = vault.0.lock().unwrap();
+  if let Some(vault) = vault.as_ref().filter(|v| !v.is_empty()) {
+    let uid = ntag.read_uid(card)
+      .map_err(|e| CommandError::from(e).context("UID olvasási hiba"))?;
+    if let Some(entry) = vault.lookup(&uid) {
+      info!("Password a tárolóból: {}", entry.label);
+      return Ok((Some(entry.password), Some(entry.pack)));
+    }
+  }
+  Ok((None, pack))
+}
+
+/// Opcionális password validálása (4 byte)
+fn parse_password(password: Option<Vec<u8>>) -> Result<Option<[u8; 4]>, CommandError> {
+  password.map(|p| {
+    if p.len() != 4 {
+      return Err(format!("A password pontosan 4 byte kell legyen! (kapott: {})", p.len()).into());
+    }
+    Ok([p[0], p[1], p[2], p[3]])
+  }).transpose()
+}
+
+/// Opcionális PACK validálása (2 byte), a PWD_AUTH válaszának ellenőrzéséhez
+fn parse_pack(pack: Option<Vec<u8>>) -> Result<Option<[u8; 2]>, CommandError> {
+  pack.map(|p| {
+    if p.len() != 2 {
+      return Err(format!("A PACK pontosan 2 byte kell legyen! (kapott: {})", p.len()).into());
+    }
+    Ok([p[0], p[1]])
+  }).transpose()
+}
+
+/// Író parancs a munkamenet címkéjén. ACR122U olvasón az eredményt LED és buzzer jelzi
+/// (batch kódolásnál a képernyő nélkül is látszik); ha nincs címke, nincs mit jelezni.
+fn write_with_feedback<T>(session: &SessionState, write: impl FnOnce(&dyn NfcCard) -> Result<T, CommandError>) -> Result<T, CommandError> {
+  let mut session = session.0.lock().unwrap();
+  let card = session.card()
+    .map_err(|e| CommandError::from(e).context("Csatlakozási hiba"))?;
+  let result = write(card);
+  signal_feedback(card, result.is_ok());
+  result
+}
+
+/// Sikeres / sikertelen írás jelzése (csak ACR122U olvasón; a jelzés hibája nem rontja el az írást)
+fn signal_feedback(card: &dyn NfcCard, success: bool) {
+  if ReaderDriver::identify(card) != ReaderDriver::Acr122u {
+    return;
+  }
+  let feedback = if success { Feedback::Success } else { Feedback::Failure };
+  if let Err(e) = acr122u::signal(card, feedback) {
+    warn!("LED/buzzer jelzés nem sikerült: {}", e);
+  }
+}
+
+/// Opcionális visszaolvasás az írás után; eltérés esetén a lapokra bontott diff a hibában
+fn verify_write(ntag: &Ntag216, card: &dyn NfcCard, written: &WrittenPages, verify: Option<bool>) -> Result<String, CommandError> {
+  if !verify.unwrap_or(false) {
+    return Ok(String::new());
+  }
+  let report = ntag.verify_written(card, written)
+    .map_err(|e| CommandError::from(e).context("Visszaolvasási hiba"))?
+    .ensure_ok()
+    .map_err(|e| CommandError::from(e).context("Ellenőrzési hiba"))?;
+  Ok(format!("\n✅ Visszaolvasva és ellenőrizve: {} lap", report.pages_checked))
+}
+
+/// URL írása NTAG216 címkére
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn write_url(url: String, password: Option<Vec<u8>>, pack: Option<Vec<u8>>, verify: Option<bool>, profile: tauri::State<KeyProfileState>, vault: tauri::State<VaultState>, session: tauri::State<SessionState>) -> Result<String, CommandError> {
+  debug!(url = %url, password = ?password.as_deref().map(Secret), "Paraméterek");
   
-  let ntag = Ntag216;
+  write_with_feedback(&session, |card| {
+    let ntag = Ntag216;
   
-  match ntag.check_type(&card) {
-    Ok(true) => {
-      let pwd_array = password.as_ref().map(|p| {
-        if p.len() != 4 {
-          return Err(format!("A password pontosan 4 byte kell legyen! (kapott: {})", p.len()));
-        }
-        Ok([p[0], p[1], p[2], p[3]])
-      }).transpose()?;
+    match ntag.check_type(card) {
+      Ok(true) => {
+        let pwd_array = parse_password(password)?;
+        let pack_array = parse_pack(pack)?;
+        let (pwd_array, pack_array) = resolve_credentials(&ntag, card, &profile, &vault, pwd_array, pack_array)?;
       
-      ntag.write_ndef_url_with_password(&card, &url, pwd_array.as_ref())
-        .map_err(|e| format!("Írási hiba: {}", e))?;
-      println!("  ✅ URL sikeresen írva");
-      Ok(format!("Sikeresen írtam az URL-t az NTAG216 címkére: {}", url))
+        let written = ntag.write_ndef_url_with_password(card, &url, pwd_array.as_ref(), pack_array.as_ref())
+          .map_err(|e| CommandError::from(e).context("Írási hiba"))?;
+        let verified = verify_write(&ntag, card, &written, verify)?;
+        info!("URL sikeresen írva");
+        Ok(format!("Sikeresen írtam az URL-t az NTAG216 címkére: {}{}", url, verified))
+      }
+      Ok(false) => Err(NfcError::NotNtag.into()),
+      Err(e) => Err(CommandError::from(e).context("Címke ellenőrzési hiba")),
     }
-    Ok(false) => Err("Ez nem egy NTAG216 címke!".to_string()),
-    Err(e) => Err(format!("Címke ellenőrzési hiba: {}", e)),
-  }
+  })
 }
 
 /// URL olvasása NTAG216 címkéből
 #[tauri::command]
-fn read_url() -> Result<String, String> {
-  let reader = NfcReader::new()
-    .map_err(|e| format!("NFC olvasó inicializálási hiba: {}", e))?;
-  
-  let card = reader.connect()
-    .map_err(|e| format!("Csatlakozási hiba: {}", e))?;
+#[tracing::instrument(skip_all)]
+fn read_url(session: tauri::State<SessionState>) -> Result<String, CommandError> {
+  let mut session = session.0.lock().unwrap();
+  let card = session.card()
+    .map_err(|e| CommandError::from(e).context("Csatlakozási hiba"))?;
   
   let ntag = Ntag216;
   
-  match ntag.check_type(&card) {
+  match ntag.check_type(card) {
     Ok(true) => {
-      match ntag.read_ndef(&card)
-        .map_err(|e| format!("Olvasási hiba: {}", e))?
+      match ntag.read_ndef(card)
+        .map_err(|e| CommandError::from(e).context("Olvasási hiba"))?
       {
         Some(url) => Ok(url),
-        None => Err("Nem található NDEF URL üzenet a címkén".to_string()),
+        None => Err(CommandError::new("no_ndef", "Nem található NDEF URL üzenet a címkén")),
       }
     }
-    Ok(false) => Err("Ez nem egy NTAG216 címke!".to_string()),
-    Err(e) => Err(format!("Címke ellenőrzési hiba: {}", e)),
+    Ok(false) => Err(NfcError::NotNtag.into()),
+    Err(e) => Err(CommandError::from(e).context("Címke ellenőrzési hiba")),
   }
 }
 
 /// NDEF Text Record írása
 #[tauri::command]
-fn write_text(text: String, language: String, password: Option<Vec<u8>>) -> Result<String, String> {
-  let reader = NfcReader::new()
-    .map_err(|e| format!("NFC olvasó inicializálási hiba: {}", e))?;
+#[allow(clippy::too_many_arguments)]
+#[tracing::instrument(skip_all)]
+fn write_text(text: String, language: String, password: Option<Vec<u8>>, pack: Option<Vec<u8>>, verify: Option<bool>, profile: tauri::State<KeyProfileState>, vault: tauri::State<VaultState>, session: tauri::State<SessionState>) -> Result<String, CommandError> {
+  write_with_feedback(&session, |card| {
+    let ntag = Ntag216;
   
-  let card = reader.connect()
-    .map_err(|e| format!("Csatlakozási hiba: {}", e))?;
-  
-  let ntag = Ntag216;
-  
-  match ntag.check_type(&card) {
-    Ok(true) => {
-      let pwd_array = password.as_ref().map(|p| {
-        if p.len() != 4 {
-          return Err(format!("A password pontosan 4 byte kell legyen! (kapott: {})", p.len()));
-        }
-        Ok([p[0], p[1], p[2], p[3]])
-      }).transpose()?;
+    match ntag.check_type(card) {
+      Ok(true) => {
+        let pwd_array = parse_password(password)?;
+        let pack_array = parse_pack(pack)?;
+        let (pwd_array, pack_array) = resolve_credentials(&ntag, card, &profile, &vault, pwd_array, pack_array)?;
       
-      ntag.write_ndef_text_with_password(&card, &text, &language, pwd_array.as_ref())
-        .map_err(|e| format!("Írási hiba: {}", e))?;
-      Ok(format!("Sikeresen írtam a szöveget az NTAG216 címkére"))
+        let written = ntag.write_ndef_text_with_password(card, &text, &language, pwd_array.as_ref(), pack_array.as_ref())
+          .map_err(|e| CommandError::from(e).context("Írási hiba"))?;
+        let verified = verify_write(&ntag, card, &written, verify)?;
+        Ok(format!("Sikeresen írtam a szöveget az NTAG216 címkére{}", verified))
+      }
+      Ok(false) => Err(NfcError::NotNtag.into()),
+      Err(e) => Err(CommandError::from(e).context("Címke ellenőrzési hiba")),
     }
-    Ok(false) => Err("Ez nem egy NTAG216 címke!".to_string()),
-    Err(e) => Err(format!("Címke ellenőrzési hiba: {}", e)),
-  }
+  })
 }
 
 /// NDEF Text Record olvasása
 #[tauri::command]
-fn read_text() -> Result<String, String> {
-  let reader = NfcReader::new()
-    .map_err(|e| format!("NFC olvasó inicializálási hiba: {}", e))?;
-  
-  let card = reader.connect()
-    .map_err(|e| format!("Csatlakozási hiba: {}", e))?;
+#[tracing::instrument(skip_all)]
+fn read_text(session: tauri::State<SessionState>) -> Result<String, CommandError> {
+  let mut session = session.0.lock().unwrap();
+  let card = session.card()
+    .map_err(|e| CommandError::from(e).context("Csatlakozási hiba"))?;
   
   let ntag = Ntag216;
   
-  match ntag.check_type(&card) {
+  match ntag.check_type(card) {
     Ok(true) => {
-      match ntag.read_ndef_text(&card)
-        .map_err(|e| format!("Olvasási hiba: {}", e))?
+      match ntag.read_ndef_text(card)
+        .map_err(|e| CommandError::from(e).context("Olvasási hiba"))?
       {
         Some((text, lang)) => Ok(format!("[{}] {}", lang, text)),
-        None => Err("Nem található NDEF Text üzenet a címkén".to_string()),
+        None => Err(CommandError::new("no_ndef", "Nem található NDEF Text üzenet a címkén")),
       }
     }
-    Ok(false) => Err("Ez nem egy NTAG216 címke!".to_string()),
-    Err(e) => Err(format!("Címke ellenőrzési hiba: {}", e)),
+    Ok(false) => Err(NfcError::NotNtag.into()),
+    Err(e) => Err(CommandError::from(e).context("Címke ellenőrzési hiba")),
   }
 }
 
 /// NDEF vCard írása
 #[tauri::command]
-fn write_vcard(vcard: String, password: Option<Vec<u8>>) -> Result<String, String> {
-  let reader = NfcReader::new()
-    .map_err(|e| format!("NFC olvasó inicializálási hiba: {}", e))?;
+#[tracing::instrument(skip_all)]
+fn write_vcard(vcard: String, password: Option<Vec<u8>>, pack: Option<Vec<u8>>, verify: Option<bool>, profile: tauri::State<KeyProfileState>, vault: tauri::State<VaultState>, session: tauri::State<SessionState>) -> Result<String, CommandError> {
+  write_with_feedback(&session, |card| {
+    let ntag = Ntag216;
   
-  let card = reader.connect()
-    .map_err(|e| format!("Csatlakozási hiba: {}", e))?;
-  
-  let ntag = Ntag216;
-  
-  match ntag.check_type(&card) {
-    Ok(true) => {
-      let pwd_array = password.as_ref().map(|p| {
-        if p.len() != 4 {
-          return Err(format!("A password pontosan 4 byte kell legyen! (kapott: {})", p.len()));
-        }
-        Ok([p[0], p[1], p[2], p[3]])
-      }).transpose()?;
+    match ntag.check_type(card) {
+      Ok(true) => {
+        let pwd_array = parse_password(password)?;
+        let pack_array = parse_pack(pack)?;
+        let (pwd_array, pack_array) = resolve_credentials(&ntag, card, &profile, &vault, pwd_array, pack_array)?;
       
-      ntag.write_ndef_vcard_with_password(&card, &vcard, pwd_array.as_ref())
-        .map_err(|e| format!("Írási hiba: {}", e))?;
-      Ok(format!("Sikeresen írtam a vCard-ot az NTAG216 címkére"))
+        let written = ntag.write_ndef_vcard_with_password(card, &vcard, pwd_array.as_ref(), pack_array.as_ref())
+          .map_err(|e| CommandError::from(e).context("Írási hiba"))?;
+        let verified = verify_write(&ntag, card, &written, verify)?;
+        Ok(format!("Sikeresen írtam a vCard-ot az NTAG216 címkére{}", verified))
+      }
+      Ok(false) => Err(NfcError::NotNtag.into()),
+      Err(e) => Err(CommandError::from(e).context("Címke ellenőrzési hiba")),
     }
-    Ok(false) => Err("Ez nem egy NTAG216 címke!".to_string()),
-    Err(e) => Err(format!("Címke ellenőrzési hiba: {}", e)),
-  }
+  })
 }
 
 /// NDEF vCard olvasása
 #[tauri::command]
-fn read_vcard() -> Result<String, String> {
-  let reader = NfcReader::new()
-    .map_err(|e| format!("NFC olvasó inicializálási hiba: {}", e))?;
-  
-  let card = reader.connect()
-    .map_err(|e| format!("Csatlakozási hiba: {}", e))?;
+#[tracing::instrument(skip_all)]
+fn read_vcard(session: tauri::State<SessionState>) -> Result<String, CommandError> {
+  let mut session = session.0.lock().unwrap();
+  let card = session.card()
+    .map_err(|e| CommandError::from(e).context("Csatlakozási hiba"))?;
   
   let ntag = Ntag216;
   
-  match ntag.check_type(&card) {
+  match ntag.check_type(card) {
     Ok(true) => {
-      match ntag.read_ndef_vcard(&card)
-        .map_err(|e| format!("Olvasási hiba: {}", e))?
+      match ntag.read_ndef_vcard(card)
+        .map_err(|e| CommandError::from(e).context("Olvasási hiba"))?
       {
         Some(vcard) => Ok(vcard),
-        None => Err("Nem található NDEF vCard üzenet a címkén".to_string()),
+        None => Err(CommandError::new("no_ndef", "Nem található NDEF vCard üzenet a címkén")),
       }
     }
-    Ok(false) => Err("Ez nem egy NTAG216 címke!".to_string()),
-    Err(e) => Err(format!("Címke ellenőrzési hiba: {}", e)),
+    Ok(false) => Err(NfcError::NotNtag.into()),
+    Err(e) => Err(CommandError::from(e).context("Címke ellenőrzési hiba")),
   }
 }
 
 /// Password beállítása
 #[tauri::command]
-fn set_password(password: Vec<u8>, pack: Vec<u8>, auth_limit: u8) -> Result<String, String> {
-  println!("🔐 set_password CALLED");
-  println!("  📥 password: {:?} (len: {})", password, password.len());
-  println!("  📥 pack: {:?} (len: {})", pack, pack.len());
-  println!("  📥 auth_limit: {}", auth_limit);
+#[tracing::instrument(skip_all)]
+fn set_password(password: Vec<u8>, pack: Vec<u8>, auth_limit: u8, profile: tauri::State<KeyProfileState>, vault: tauri::State<VaultState>, session: tauri::State<SessionState>) -> Result<String, CommandError> {
+  debug!(password = ?Secret(&password), pack = ?pack, auth_limit, "Paraméterek");
   
   if password.len() != 4 {
-    println!("  ❌ Password hossz hiba: {} != 4", password.len());
-    return Err(format!("A password pontosan 4 byte kell legyen! (kapott: {})", password.len()));
+    warn!("Password hossz hiba: {} != 4", password.len());
+    return Err(format!("A password pontosan 4 byte kell legyen! (kapott: {})", password.len()).into());
   }
   if pack.len() != 2 {
-    println!("  ❌ PACK hossz hiba: {} != 2", pack.len());
-    return Err(format!("A PACK pontosan 2 byte kell legyen! (kapott: {})", pack.len()));
+    warn!("PACK hossz hiba: {} != 2", pack.len());
+    return Err(format!("A PACK pontosan 2 byte kell legyen! (kapott: {})", pack.len()).into());
   }
   
-  println!("  ✅ Paraméterek validálva");
-  
-  println!("  🔌 NFC olvasó inicializálása...");
-  let reader = NfcReader::new()
-    .map_err(|e| {
-      println!("  ❌ NFC olvasó hiba: {}", e);
-      format!("NFC olvasó inicializálási hiba: {}", e)
-    })?;
-  println!("  ✅ NFC olvasó inicializálva");
+  write_with_feedback(&session, |card| {
+    let ntag = Ntag216;
   
-  println!("  📡 Címke csatlakoztatása...");
-  let card = reader.connect()
-    .map_err(|e| {
-      println!("  ❌ Csatlakozási hiba: {}", e);
-      format!("Csatlakozási hiba: {}", e)
-    })?;
-  println!("  ✅ Címke csatlakoztatva");
-  
-  let ntag = Ntag216;
+    let diversified = diversified_key(&ntag, card, &profile)?;
+    let (pwd_array, pack_array) = match diversified {
+      Some(key) => (key.password, key.pack),
+      None => ([password[0], password[1], password[2], password[3]], [pack[0], pack[1]]),
+    };
   
-  println!("  🔍 NTAG216 típus ellenőrzése...");
-  // Próbáljuk meg password nélkül, ha nem működik, akkor lehet hogy már password védett
-  match ntag.check_type(&card) {
-    Ok(true) => {
-      println!("  ✅ NTAG216 címke megerősítve");
-      let pwd_array: [u8; 4] = [password[0], password[1], password[2], password[3]];
-      let pack_array: [u8; 2] = [pack[0], pack[1]];
+    debug!("NTAG216 típus ellenőrzése...");
+    // Próbáljuk meg password nélkül, ha nem működik, akkor lehet hogy már password védett
+    match ntag.check_type(card) {
+      Ok(true) => {
+        debug!("NTAG216 címke megerősítve");
       
-      println!("  📝 Password beállítása...");
-      println!("    Password: {:02X?}", pwd_array);
-      println!("    PACK: {:02X?}", pack_array);
-      println!("    Auth Limit: {}", auth_limit);
+        info!(password = ?Secret(&pwd_array), pack = ?pack_array, auth_limit, "Password beállítása...");
       
-      ntag.set_password(&card, &pwd_array, &pack_array, auth_limit)
-        .map_err(|e| {
-          println!("  ❌ Password beállítási hiba: {}", e);
-          format!("Password beállítási hiba: {}", e)
-        })?;
-      println!("  ✅ Password sikeresen beállítva");
-      Ok(format!("Sikeresen beállítottam a password védelmet"))
-    }
-    Ok(false) => {
-      println!("  ❌ Ez nem egy NTAG216 címke!");
-      Err("Ez nem egy NTAG216 címke!".to_string())
-    },
-    Err(e) => {
-      // Ha password védelem aktív, akkor lehet hogy már be van állítva
-      let error_msg = format!("{}", e);
-      if error_msg.contains("SW1=0x63") || error_msg.contains("Password védelem aktív") {
-        println!("  ⚠️ Password védelem aktív lehet, próbáljuk meg password-dal...");
-        let pwd_array: [u8; 4] = [password[0], password[1], password[2], password[3]];
-        match ntag.check_type_with_password(&card, Some(&pwd_array)) {
-          Ok(true) => {
-            println!("  ✅ NTAG216 címke megerősítve (password-dal)");
-            println!("  💡 A password már be van állítva, vagy módosítsd a 'Password Eltávolítása' gombbal.");
-            Err("A password már be van állítva a címkére! Használd a 'Password Eltávolítása' gombot, ha újra be szeretnéd állítani.".to_string())
-          }
-          Ok(false) => Err("Ez nem egy NTAG216 címke!".to_string()),
-          Err(e2) => {
-            println!("  ❌ Címke ellenőrzési hiba (password-dal is): {}", e2);
-            Err(format!("Címke ellenőrzési hiba: {}", e2))
+        ntag.set_password(card, &pwd_array, &pack_array, auth_limit)
+          .map_err(|e| {
+            warn!("Password beállítási hiba: {}", e);
+            CommandError::from(e).context("Password beállítási hiba")
+          })?;
+        info!("Password sikeresen beállítva");
+      
+        // Kézzel megadott password esetén megjegyezzük a tárolóban (ha fel van oldva),
+        // a diverzifikált password-ok bármikor újra származtathatók
+        if diversified.is_none() {
+          if let Some(vault) = vault.0.lock().unwrap().as_mut() {
+            let uid = ntag.read_uid(card)
+              .map_err(|e| CommandError::from(e).context("UID olvasási hiba"))?;
+            let uid_hex = vault::uid_to_hex(&uid);
+            vault.add(VaultEntry {
+              label: uid_hex.clone(),
+              uids: vec![uid_hex],
+              password: pwd_array,
+              pack: pack_array,
+            }).map_err(|e| CommandError::from(e).context("Tároló mentési hiba"))?;
+            info!("Password elmentve a tárolóba");
           }
         }
-      } else {
-        println!("  ❌ Címke ellenőrzési hiba: {}", e);
-        Err(format!("Címke ellenőrzési hiba: {}", e))
+        Ok(format!("Sikeresen beállítottam a password védelmet"))
       }
-    },
-  }
+      Ok(false) => {
+        warn!("Ez nem egy NTAG216 címke!");
+        Err(NfcError::NotNtag.into())
+      },
+      Err(e) => {
+        // Ha password védelem aktív, akkor lehet hogy már be van állítva
+        if e.is_auth_failure() {
+          warn!("Password védelem aktív lehet, próbáljuk meg password-dal...");
+          match ntag.check_type_with_password(card, Some(&pwd_array)) {
+            Ok(true) => {
+              info!("NTAG216 címke megerősítve (password-dal): a password már be van állítva");
+              Err(CommandError::new(
+                "password_already_set",
+                "A password már be van állítva a címkére! Használd a 'Password Eltávolítása' gombot, ha újra be szeretnéd állítani.",
+              ))
+            }
+            Ok(false) => Err(NfcError::NotNtag.into()),
+            Err(e2) => {
+              warn!("Címke ellenőrzési hiba (password-dal is): {}", e2);
+              Err(CommandError::from(e2).context("Címke ellenőrzési hiba"))
+            }
+          }
+        } else {
+          warn!("Címke ellenőrzési hiba: {}", e);
+          Err(CommandError::from(e).context("Címke ellenőrzési hiba"))
+        }
+      },
+    }
+  })
 }
 
 /// Password védelem eltávolítása
+///
+/// A jelenlegi password a `set_password`-höz hasonlóan a kulcs profilból, a megadott
+/// értékből vagy a tárolóból jön.
 #[tauri::command]
-fn remove_password() -> Result<String, String> {
-  let reader = NfcReader::new()
-    .map_err(|e| format!("NFC olvasó inicializálási hiba: {}", e))?;
+#[tracing::instrument(skip_all)]
+fn remove_password(password: Option<Vec<u8>>, pack: Option<Vec<u8>>, profile: tauri::State<KeyProfileState>, vault: tauri::State<VaultState>, session: tauri::State<SessionState>) -> Result<String, CommandError> {
+  debug!(password = ?password.as_deref().map(Secret), pack = ?pack, "Paraméterek");
   
-  let card = reader.connect()
-    .map_err(|e| format!("Csatlakozási hiba: {}", e))?;
+  write_with_feedback(&session, |card| {
+    let ntag = Ntag216;
   
-  let ntag = Ntag216;
+    match ntag.check_type(card) {
+      Ok(true) => {
+        let pwd_array = parse_password(password)?;
+        let pack_array = parse_pack(pack)?;
+        let (pwd_array, pack_array) = resolve_credentials(&ntag, card, &profile, &vault, pwd_array, pack_array)?;
+        let pwd_array = pwd_array.ok_or_else(|| {
+          CommandError::from(NfcError::AuthRequired).context("A password eltávolításához meg kell adni a jelenlegi password-ot")
+        })?;
+      
+        ntag.remove_password(card, &pwd_array, pack_array.as_ref())
+          .map_err(|e| CommandError::from(e).context("Password eltávolítási hiba"))?;
+        Ok(format!("Sikeresen eltávolítottam a password védelmet"))
+      }
+      Ok(false) => Err(NfcError::NotNtag.into()),
+      Err(e) => Err(CommandError::from(e).context("Címke ellenőrzési hiba")),
+    }
+  })
+}
+
+/// Visszafordíthatatlan művelet válasza: vagy megerősítést kér, vagy végrehajtotta a műveletet
+#[derive(Debug, Serialize)]
+struct IrreversibleResponse {
+  executed: bool,
+  challenge: Option<Challenge>,
+  message: String,
+}
+
+/// Kétlépcsős zárolás: token nélkül csak megerősítő tokent ad a címke UID-jához és a
+/// tervhez kötve, érvényes tokennel végrehajtja a tervet
+fn guarded_lock(
+  ntag: &Ntag216,
+  card: &dyn NfcCard,
+  operation: &'static str,
+  plan: &LockPlan,
+  confirmation_token: Option<String>,
+  guard: &ConfirmationGuard,
+) -> Result<IrreversibleResponse, CommandError> {
+  let uid = ntag.read_uid(card)
+    .map_err(|e| CommandError::from(e).context("UID olvasási hiba"))?;
   
-  match ntag.check_type(&card) {
-    Ok(true) => {
-      ntag.remove_password(&card)
-        .map_err(|e| format!("Password eltávolítási hiba: {}", e))?;
-      Ok(format!("Sikeresen eltávolítottam a password védelmet"))
+  match confirmation_token {
+    None => {
+      let challenge = guard.issue(operation, Subject::Tag(uid), plan);
+      Ok(IrreversibleResponse {
+        executed: false,
+        message: format!(
+          "Megerősítés szükséges {} másodpercen belül!\n{}",
+          challenge.expires_in_secs,
+          plan.report()
+        ),
+        challenge: Some(challenge),
+      })
+    }
+    Some(token) => {
+      guard.redeem(&token, operation, &Subject::Tag(uid), plan)
+        .map_err(|e| CommandError::from(e).context("Megerősítési hiba"))?;
+      let applied = ntag.apply_lock(card, plan)
+        .map_err(|e| CommandError::from(e).context("Zárolási hiba"));
+      signal_feedback(card, applied.is_ok());
+      applied?;
+      Ok(IrreversibleResponse {
+        executed: true,
+        challenge: None,
+        message: format!("⚠️ Zárolás kész! VISSZAFORDÍTHATATLAN!\n{}", plan.report()),
+      })
     }
-    Ok(false) => Err("Ez nem egy NTAG216 címke!".to_string()),
-    Err(e) => Err(format!("Címke ellenőrzési hiba: {}", e)),
   }
 }
 
 /// Read-only mód beállítása (VISSZAFORDÍTHATATLAN!)
+/// Első hívás: megerősítő token; második hívás a tokennel: végrehajtás
 #[tauri::command]
-fn set_read_only() -> Result<String, String> {
-  let reader = NfcReader::new()
-    .map_err(|e| format!("NFC olvasó inicializálási hiba: {}", e))?;
-  
-  let card = reader.connect()
-    .map_err(|e| format!("Csatlakozási hiba: {}", e))?;
+#[tracing::instrument(skip_all)]
+fn set_read_only(confirmation_token: Option<String>, guard: tauri::State<ConfirmationGuard>, session: tauri::State<SessionState>) -> Result<IrreversibleResponse, CommandError> {
+  let mut session = session.0.lock().unwrap();
+  let card = session.card()
+    .map_err(|e| CommandError::from(e).context("Csatlakozási hiba"))?;
   
   let ntag = Ntag216;
   
-  match ntag.check_type(&card) {
+  match ntag.check_type(card) {
     Ok(true) => {
-      ntag.set_read_only(&card)
-        .map_err(|e| format!("Read-only beállítási hiba: {}", e))?;
-      Ok(format!("⚠️ Read-only mód beállítva! VISSZAFORDÍTHATATLAN!"))
+      let chip = ntag.detect_chip(card)
+        .map_err(|e| CommandError::from(e).context("Címke ellenőrzési hiba"))?;
+      let plan = ntag.plan_lock(card, &LockRequest::read_only(chip))
+        .map_err(|e| CommandError::from(e).context("Read-only beállítási hiba"))?;
+      guarded_lock(&ntag, card, "set_read_only", &plan, confirmation_token, &guard)
     }
-    Ok(false) => Err("Ez nem egy NTAG216 címke!".to_string()),
-    Err(e) => Err(format!("Címke ellenőrzési hiba: {}", e)),
+    Ok(false) => Err(NfcError::NotNtag.into()),
+    Err(e) => Err(CommandError::from(e).context("Címke ellenőrzési hiba")),
   }
 }
 
-/// Konfiguráció olvasása
+/// Zárolási kérés a frontend paramétereiből
+fn lock_request(ranges: Vec<(u8, u8)>, lock_config: bool, cc_read_only: bool, freeze_lock_bits: bool) -> LockRequest {
+  LockRequest {
+    ranges,
+    lock_config,
+    cc_read_only,
+    freeze_lock_bits,
+  }
+}
+
+/// Zárolás dry-run: megmutatja, mely lapok válnak véglegesen read-only-vá (nem ír a címkére)
 #[tauri::command]
-fn read_config() -> Result<String, String> {
-  let reader = NfcReader::new()
-    .map_err(|e| format!("NFC olvasó inicializálási hiba: {}", e))?;
+#[tracing::instrument(skip_all)]
+fn plan_lock(ranges: Vec<(u8, u8)>, lock_config: bool, cc_read_only: bool, freeze_lock_bits: bool, session: tauri::State<SessionState>) -> Result<String, CommandError> {
+  let mut session = session.0.lock().unwrap();
+  let card = session.card()
+    .map_err(|e| CommandError::from(e).context("Csatlakozási hiba"))?;
   
-  let card = reader.connect()
-    .map_err(|e| format!("Csatlakozási hiba: {}", e))?;
+  let ntag = Ntag216;
+  let request = lock_request(ranges, lock_config, cc_read_only, freeze_lock_bits);
+  
+  let plan = ntag.plan_lock(card, &request)
+    .map_err(|e| CommandError::from(e).context("Zárolási terv hiba"))?;
+  Ok(plan.report())
+}
+
+/// Lapok végleges zárolása lock bitekkel és/vagy CFGLCK-val (VISSZAFORDÍTHATATLAN!)
+/// Első hívás: megerősítő token; második hívás a tokennel: végrehajtás
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn apply_lock(
+  ranges: Vec<(u8, u8)>,
+  lock_config: bool,
+  cc_read_only: bool,
+  freeze_lock_bits: bool,
+  confirmation_token: Option<String>,
+  guard: tauri::State<ConfirmationGuard>,
+  session: tauri::State<SessionState>,
+) -> Result<IrreversibleResponse, CommandError> {
+  let mut session = session.0.lock().unwrap();
+  let card = session.card()
+    .map_err(|e| CommandError::from(e).context("Csatlakozási hiba"))?;
   
   let ntag = Ntag216;
+  let request = lock_request(ranges, lock_config, cc_read_only, freeze_lock_bits);
   
-  match ntag.check_type(&card) {
+  let plan = ntag.plan_lock(card, &request)
+    .map_err(|e| CommandError::from(e).context("Zárolási terv hiba"))?;
+  guarded_lock(&ntag, card, "apply_lock", &plan, confirmation_token, &guard)
+}
+
+/// Konfiguráció olvasása
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn read_config(session: tauri::State<SessionState>) -> Result<String, CommandError> {
+  let mut session = session.0.lock().unwrap();
+  let card = session.card()
+    .map_err(|e| CommandError::from(e).context("Csatlakozási hiba"))?;
+  
+  let ntag = Ntag216;
+  
+  match ntag.check_type(card) {
     Ok(true) => {
-      let config = ntag.read_config(&card)
-        .map_err(|e| format!("Konfiguráció olvasási hiba: {}", e))?;
-      
-      let pwd_str = format!("{:02X}{:02X}{:02X}{:02X}", 
-        config.password[0], config.password[1], config.password[2], config.password[3]);
-      let pack_str = format!("{:02X}{:02X}", config.pack[0], config.pack[1]);
+      let config = ntag.read_config(card)
+        .map_err(|e| CommandError::from(e).context("Konfiguráció olvasási hiba"))?;
       
       Ok(format!(
-        "Password: {}\nPACK: {}\nAuth Limit: {}\nPassword Védett: {}\nRead-Only: {}\nLocked: {}",
-        pwd_str,
-        pack_str,
+        "Chip: {:?}\nAUTH0: 0x{:02X}\nACCESS: 0x{:02X}\nAuth Limit: {}\nPassword Védett: {}\nOlvasás is védett: {}\nKonfiguráció zárolva: {}",
+        config.chip,
+        config.auth0,
+        config.access,
         config.auth_limit,
         if config.password_protected { "Igen" } else { "Nem" },
-        if config.read_only { "Igen" } else { "Nem" },
-        if config.locked { "Igen ⚠️" } else { "Nem" }
+        if config.read_protected { "Igen" } else { "Nem" },
+        if config.config_locked { "Igen ⚠️" } else { "Nem" }
       ))
     }
-    Ok(false) => Err("Ez nem egy NTAG216 címke!".to_string()),
-    Err(e) => Err(format!("Címke ellenőrzési hiba: {}", e)),
+    Ok(false) => Err(NfcError::NotNtag.into()),
+    Err(e) => Err(CommandError::from(e).context("Címke ellenőrzési hiba")),
   }
 }
 
-/// NDEF üzenet törlése
+/// ACR122U olvasó állapota
+#[derive(Debug, Serialize)]
+struct Acr122uStatus {
+  firmware: String,
+  picc: PiccParameters,
+}
+
+/// A munkamenet kártyája, ha az olvasó ACR122U (az escape parancsok csak ott értelmesek)
+fn acr122u_card(session: &mut NfcSession) -> Result<&dyn NfcCard, CommandError> {
+  let card = session.card()
+    .map_err(|e| CommandError::from(e).context("Csatlakozási hiba"))?;
+  if ReaderDriver::identify(card) != ReaderDriver::Acr122u {
+    return Err(CommandError::new("unsupported_reader", "Ez a funkció csak ACR122U olvasóval érhető el"));
+  }
+  Ok(card)
+}
+
+/// ACR122U firmware verzió és PICC működési paraméterek
 #[tauri::command]
-fn clear_ndef(password: Option<Vec<u8>>) -> Result<String, String> {
-  let reader = NfcReader::new()
-    .map_err(|e| format!("NFC olvasó inicializálási hiba: {}", e))?;
+#[tracing::instrument(skip_all)]
+fn acr122u_status(session: tauri::State<SessionState>) -> Result<Acr122uStatus, CommandError> {
+  let mut session = session.0.lock().unwrap();
+  let card = acr122u_card(&mut session)?;
+  let firmware = acr122u::firmware_version(card)
+    .map_err(|e| CommandError::from(e).context("Firmware lekérdezési hiba"))?;
+  let picc = acr122u::picc_parameters(card)
+    .map_err(|e| CommandError::from(e).context("PICC paraméter hiba"))?;
+  Ok(Acr122uStatus { firmware, picc })
+}
+
+/// ACR122U beállítások: automatikus címke keresés, sípolás címke felismeréskor, antenna.
+/// A None értékű beállítások nem változnak.
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn acr122u_configure(auto_polling: Option<bool>, detection_buzzer: Option<bool>, antenna: Option<bool>, session: tauri::State<SessionState>) -> Result<String, CommandError> {
+  let mut session = session.0.lock().unwrap();
+  let card = acr122u_card(&mut session)?;
+  let mut changes = Vec::new();
   
-  let card = reader.connect()
-    .map_err(|e| format!("Csatlakozási hiba: {}", e))?;
+  if let Some(auto_polling) = auto_polling {
+    let current = acr122u::picc_parameters(card)
+      .map_err(|e| CommandError::from(e).context("PICC paraméter hiba"))?;
+    let updated = acr122u::set_picc_parameters(card, PiccParameters { auto_polling, ..current })
+      .map_err(|e| CommandError::from(e).context("PICC paraméter hiba"))?;
+    changes.push(format!("Automatikus címke keresés: {}", if updated.auto_polling { "be" } else { "ki" }));
+  }
+  if let Some(enabled) = detection_buzzer {
+    acr122u::set_detection_buzzer(card, enabled)
+      .map_err(|e| CommandError::from(e).context("Buzzer beállítási hiba"))?;
+    changes.push(format!("Sípolás címke felismeréskor: {}", if enabled { "be" } else { "ki" }));
+  }
+  if let Some(on) = antenna {
+    acr122u::set_antenna(card, on)
+      .map_err(|e| CommandError::from(e).context("Antenna hiba"))?;
+    changes.push(format!("Antenna: {}", if on { "be" } else { "ki" }));
+  }
+  
+  if changes.is_empty() {
+    return Ok("Nincs módosítandó beállítás".to_string());
+  }
+  Ok(format!("ACR122U beállítva:\n{}", changes.join("\n")))
+}
+
+/// Címke információk olvasása natív parancsokkal (az olvasó driverén keresztül)
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn read_tag_info(session: tauri::State<SessionState>) -> Result<TagInfo, CommandError> {
+  let mut session = session.0.lock().unwrap();
+  let card = session.card()
+    .map_err(|e| CommandError::from(e).context("Csatlakozási hiba"))?;
   
   let ntag = Ntag216;
+  let driver = ReaderDriver::identify(card);
+  let version = ntag.get_version(card)
+    .map_err(|e| CommandError::from(e).context("GET_VERSION hiba"))?;
   
-  match ntag.check_type(&card) {
-    Ok(true) => {
-      let pwd_array = password.as_ref().map(|p| {
-        if p.len() != 4 {
-          return Err(format!("A password pontosan 4 byte kell legyen! (kapott: {})", p.len()));
-        }
-        Ok([p[0], p[1], p[2], p[3]])
-      }).transpose()?;
-      
-      ntag.clear_ndef_with_password(&card, pwd_array.as_ref())
-        .map_err(|e| format!("Törlési hiba: {}", e))?;
-      Ok(format!("Sikeresen töröltem az NDEF üzenetet"))
+  let signature = match ntag.read_signature(card) {
+    Ok(sig) => Some(sig.iter().map(|b| format!("{:02X}", b)).collect()),
+    Err(e) => {
+      warn!("READ_SIG nem sikerült: {}", e);
+      None
     }
-    Ok(false) => Err("Ez nem egy NTAG216 címke!".to_string()),
-    Err(e) => Err(format!("Címke ellenőrzési hiba: {}", e)),
+  };
+  let counter = match ntag.read_counter(card) {
+    Ok(counter) => counter,
+    Err(e) => {
+      warn!("READ_CNT nem sikerült: {}", e);
+      None
+    }
+  };
+  
+  // Kikapcsolt számlálónál a READ_CNT NAK-ot kap, a címke HALT állapotba kerül:
+  // kiválasztjuk újra, hogy a következő parancs ne a HALT-olt címkének menjen
+  if counter.is_none() {
+    session.reconnect()
+      .map_err(|e| CommandError::from(e).context("Újracsatlakozási hiba"))?;
   }
+  
+  Ok(TagInfo { driver, version, signature, counter })
 }
 
-/// Raw byte írása
+/// A legutóbbi log sorok (hibajelentésekhez); a password-ök a logban sem szerepelnek
 #[tauri::command]
-fn write_raw(start_block: u8, data: Vec<u8>, password: Option<Vec<u8>>) -> Result<String, String> {
-  let reader = NfcReader::new()
-    .map_err(|e| format!("NFC olvasó inicializálási hiba: {}", e))?;
+#[tracing::instrument(skip_all)]
+fn get_logs(limit: Option<usize>) -> Vec<String> {
+  logging::recent(limit.unwrap_or(500))
+}
+
+/// Nyers natív parancs a címkének (szakértői konzol), pl. `30 04` vagy `3A 00 0F`
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn transceive_raw(command: Vec<u8>, console: tauri::State<ExpertConsole>, session: tauri::State<SessionState>) -> Result<ConsoleEntry, CommandError> {
+  if !console.is_enabled() {
+    return Err(CommandError::new("expert_mode_disabled", "A nyers parancsokhoz kapcsold be a szakértői módot"));
+  }
+  if command.is_empty() || command.len() > 250 {
+    return Err(format!("A parancs 1-250 byte lehet (kapott: {})", command.len()).into());
+  }
   
-  let card = reader.connect()
-    .map_err(|e| format!("Csatlakozási hiba: {}", e))?;
+  let mut session = session.0.lock().unwrap();
+  let card = session.card()
+    .map_err(|e| CommandError::from(e).context("Csatlakozási hiba"))?;
+  Ok(console.transceive(card, &command))
+}
+
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn console_history(console: tauri::State<ExpertConsole>) -> Vec<ConsoleEntry> {
+  console.history()
+}
+
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn clear_console_history(console: tauri::State<ExpertConsole>) {
+  console.clear_history();
+}
+
+/// Szakértői mód be- és kikapcsolása; a választás mentésre kerül
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn set_expert_mode(enabled: bool, app: tauri::AppHandle, console: tauri::State<ExpertConsole>) -> Result<String, CommandError> {
+  let path = settings_path(&app)?;
+  let mut settings = Settings::load(&path);
+  settings.expert_mode = enabled;
+  settings.save(&path)
+    .map_err(|e| CommandError::from(e).context("Beállítások mentési hiba"))?;
   
-  let ntag = Ntag216;
+  console.set_enabled(enabled);
+  Ok(if enabled { "Szakértői mód bekapcsolva" } else { "Szakértői mód kikapcsolva" }.to_string())
+}
+
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn get_expert_mode(console: tauri::State<ExpertConsole>) -> bool {
+  console.is_enabled()
+}
+
+/// APDU trace felvétel indítása (JSON lines); útvonal nélkül az adatkönyvtár traces mappájába.
+/// A password byte-ok csak `plaintext_passwords` esetén kerülnek nyersen a fájlba.
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn start_trace(path: Option<String>, plaintext_passwords: Option<bool>, app: tauri::AppHandle, session: tauri::State<SessionState>) -> Result<String, CommandError> {
+  let path = match path {
+    Some(path) if !path.trim().is_empty() => std::path::PathBuf::from(path.trim()),
+    _ => {
+      let started = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+      app.path_resolver()
+        .app_data_dir()
+        .map(|dir| dir.join("traces").join(format!("trace-{}.jsonl", started)))
+        .ok_or_else(|| CommandError::new("error", "Nem található az alkalmazás adatkönyvtára"))?
+    }
+  };
   
-  match ntag.check_type(&card) {
-    Ok(true) => {
-      let pwd_array = password.as_ref().map(|p| {
-        if p.len() != 4 {
-          return Err(format!("A password pontosan 4 byte kell legyen! (kapott: {})", p.len()));
-        }
-        Ok([p[0], p[1], p[2], p[3]])
-      }).transpose()?;
+  let plaintext_passwords = plaintext_passwords.unwrap_or(false);
+  session.0.lock().unwrap().start_trace(&path, plaintext_passwords)
+    .map_err(|e| CommandError::from(e).context("Trace indítási hiba"))?;
+  if plaintext_passwords {
+    return Ok(format!("APDU trace felvétel (nyers password-ökkel!): {}", path.display()));
+  }
+  Ok(format!("APDU trace felvétel: {}", path.display()))
+}
+
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn stop_trace(session: tauri::State<SessionState>) -> Result<String, CommandError> {
+  match session.0.lock().unwrap().stop_trace() {
+    Some(path) => Ok(format!("Trace mentve: {}", path.display())),
+    None => Err(CommandError::new("invalid_argument", "Nincs folyamatban trace felvétel")),
+  }
+}
+
+/// Az éppen írt trace fájl (None: nincs felvétel)
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn trace_status(session: tauri::State<SessionState>) -> Option<String> {
+  session.0.lock().unwrap().trace_path().map(|path| path.display().to_string())
+}
+
+/// Rögzített trace visszajátszása a valódi címke helyett (hibajelentések reprodukálása)
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn replay_trace(path: String, session: tauri::State<SessionState>) -> Result<String, CommandError> {
+  let card = ReplayCard::load(std::path::Path::new(path.trim()))
+    .map_err(|e| CommandError::from(e).context("Trace betöltési hiba"))?;
+  session.0.lock().unwrap().replay(card);
+  Ok(format!("Trace visszajátszás: {}", path.trim()))
+}
+
+/// NDEF üzenet törlése
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn clear_ndef(password: Option<Vec<u8>>, pack: Option<Vec<u8>>, verify: Option<bool>, profile: tauri::State<KeyProfileState>, vault: tauri::State<VaultState>, session: tauri::State<SessionState>) -> Result<String, CommandError> {
+  write_with_feedback(&session, |card| {
+    let ntag = Ntag216;
+  
+    match ntag.check_type(card) {
+      Ok(true) => {
+        let pwd_array = parse_password(password)?;
+        let pack_array = parse_pack(pack)?;
+        let (pwd_array, pack_array) = resolve_credentials(&ntag, card, &profile, &vault, pwd_array, pack_array)?;
       
-      ntag.write_raw_bytes_with_password(&card, start_block, &data, pwd_array.as_ref())
-        .map_err(|e| format!("Írási hiba: {}", e))?;
-      Ok(format!("Sikeresen írtam {} byte-ot block {}-tól", data.len(), start_block))
+        let written = ntag.clear_ndef_with_password(card, pwd_array.as_ref(), pack_array.as_ref())
+          .map_err(|e| CommandError::from(e).context("Törlési hiba"))?;
+        let verified = verify_write(&ntag, card, &written, verify)?;
+        Ok(format!("Sikeresen töröltem az NDEF üzenetet{}", verified))
+      }
+      Ok(false) => Err(NfcError::NotNtag.into()),
+      Err(e) => Err(CommandError::from(e).context("Címke ellenőrzési hiba")),
     }
-    Ok(false) => Err("Ez nem egy NTAG216 címke!".to_string()),
-    Err(e) => Err(format!("Címke ellenőrzési hiba: {}", e)),
-  }
+  })
 }
 
-/// Raw byte olvasása
+/// Raw byte írása
 #[tauri::command]
-fn read_raw(start_block: u8, count: u8) -> Result<String, String> {
-  let reader = NfcReader::new()
-    .map_err(|e| format!("NFC olvasó inicializálási hiba: {}", e))?;
+#[allow(clippy::too_many_arguments)]
+#[tracing::instrument(skip_all, fields(start_block, len = data.len()))]
+fn write_raw(start_block: u8, data: Vec<u8>, password: Option<Vec<u8>>, pack: Option<Vec<u8>>, verify: Option<bool>, profile: tauri::State<KeyProfileState>, vault: tauri::State<VaultState>, session: tauri::State<SessionState>) -> Result<String, CommandError> {
+  write_with_feedback(&session, |card| {
+    let ntag = Ntag216;
   
-  let card = reader.connect()
-    .map_err(|e| format!("Csatlakozási hiba: {}", e))?;
+    match ntag.check_type(card) {
+      Ok(true) => {
+        let pwd_array = parse_password(password)?;
+        let pack_array = parse_pack(pack)?;
+        let (pwd_array, pack_array) = resolve_credentials(&ntag, card, &profile, &vault, pwd_array, pack_array)?;
+      
+        let written = ntag.write_raw_bytes_with_password(card, start_block, &data, pwd_array.as_ref(), pack_array.as_ref())
+          .map_err(|e| CommandError::from(e).context("Írási hiba"))?;
+        let verified = verify_write(&ntag, card, &written, verify)?;
+        Ok(format!("Sikeresen írtam {} byte-ot block {}-tól{}", data.len(), start_block, verified))
+      }
+      Ok(false) => Err(NfcError::NotNtag.into()),
+      Err(e) => Err(CommandError::from(e).context("Címke ellenőrzési hiba")),
+    }
+  })
+}
+
+/// Raw byte olvasása
+#[tauri::command]
+#[tracing::instrument(skip_all, fields(start_block, count))]
+fn read_raw(start_block: u8, count: u8, session: tauri::State<SessionState>) -> Result<String, CommandError> {
+  let mut session = session.0.lock().unwrap();
+  let card = session.card()
+    .map_err(|e| CommandError::from(e).context("Csatlakozási hiba"))?;
   
   let ntag = Ntag216;
   
-  match ntag.check_type(&card) {
+  match ntag.check_type(card) {
     Ok(true) => {
-      let data = ntag.read_raw_bytes(&card, start_block, count)
-        .map_err(|e| format!("Olvasási hiba: {}", e))?;
+      let data = match ntag.read_raw_bytes(card, start_block, count) {
+        // A FAST_READ-re kapott NAK után a címke HALT állapotban van: újra kiválasztjuk,
+        // és lapról lapra olvasunk
+        Err(e) if e.leaves_tag_halted() => {
+          warn!("FAST_READ NAK ({}), újracsatlakozás után lapról lapra olvasunk", e);
+          let card = session.reconnect()
+            .map_err(|e| CommandError::from(e).context("Újracsatlakozási hiba"))?;
+          ntag.read_raw_bytes_by_page(card, start_block, count)
+        }
+        result => result,
+      }
+      .map_err(|e| CommandError::from(e).context("Olvasási hiba"))?;
       
       let hex_str: Vec<String> = data.iter().map(|b| format!("{:02X}", b)).collect();
       Ok(hex_str.join(" "))
     }
-    Ok(false) => Err("Ez nem egy NTAG216 címke!".to_string()),
-    Err(e) => Err(format!("Címke ellenőrzési hiba: {}", e)),
+    Ok(false) => Err(NfcError::NotNtag.into()),
+    Err(e) => Err(CommandError::from(e).context("Címke ellenőrzési hiba")),
+  }
+}
+
+/// Mester kulcs profil kiválasztása: ezután minden password a címke UID-jából származik
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn select_key_profile(name: String, master_key: Vec<u8>, profile: tauri::State<KeyProfileState>) -> Result<String, CommandError> {
+  let key_profile = KeyProfile::new(&name, master_key)
+    .map_err(|e| CommandError::from(e).context("Profil hiba"))?;
+  *profile.0.lock().unwrap() = Some(key_profile);
+  Ok(format!("Mester kulcs profil kiválasztva: {}", name))
+}
+
+/// Mester kulcs profil kikapcsolása (ismét a kézzel megadott password-ok érvényesek)
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn clear_key_profile(profile: tauri::State<KeyProfileState>) -> Result<String, CommandError> {
+  *profile.0.lock().unwrap() = None;
+  Ok("Mester kulcs profil kikapcsolva".to_string())
+}
+
+/// A jelszó tároló fájl helye az alkalmazás adatkönyvtárában
+fn vault_path(app: &tauri::AppHandle) -> Result<std::path::PathBuf, CommandError> {
+  app.path_resolver()
+    .app_data_dir()
+    .map(|dir| dir.join("vault.json"))
+    .ok_or_else(|| CommandError::new("error", "Nem található az alkalmazás adatkönyvtára"))
+}
+
+/// Új, üres jelszó tároló létrehozása a megadott jelmondattal (a meglévőt nem írja felül)
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn vault_create(passphrase: String, app: tauri::AppHandle, vault: tauri::State<VaultState>) -> Result<String, CommandError> {
+  let path = vault_path(&app)?;
+  let created = Vault::create(&path, &passphrase)
+    .map_err(|e| CommandError::from(e).context("Tároló létrehozási hiba"))?;
+  *vault.0.lock().unwrap() = Some(created);
+  Ok("Tároló létrehozva és feloldva".to_string())
+}
+
+/// Meglévő jelszó tároló feloldása
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn vault_unlock(passphrase: String, app: tauri::AppHandle, vault: tauri::State<VaultState>) -> Result<String, CommandError> {
+  let path = vault_path(&app)?;
+  if !path.exists() {
+    return Err(CommandError::new("vault_missing", "Még nincs jelszó tároló: hozd létre a Tároló Létrehozása gombbal"));
+  }
+  let opened = Vault::open(&path, &passphrase)
+    .map_err(|e| CommandError::from(e).context("Tároló feloldási hiba"))?;
+  let count = opened.list().len();
+  *vault.0.lock().unwrap() = Some(opened);
+  Ok(format!("Tároló feloldva ({} bejegyzés)", count))
+}
+
+/// Jelszó tároló lezárása (a kulcs törlődik a memóriából)
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn vault_lock(vault: tauri::State<VaultState>) -> Result<String, CommandError> {
+  *vault.0.lock().unwrap() = None;
+  Ok("Tároló lezárva".to_string())
+}
+
+/// Bejegyzés hozzáadása. Ha nincs UID megadva, az olvasón lévő címke UID-ját használjuk.
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn vault_add(label: String, uids: Vec<String>, password: Vec<u8>, pack: Vec<u8>, vault: tauri::State<VaultState>, session: tauri::State<SessionState>) -> Result<String, CommandError> {
+  if password.len() != 4 {
+    return Err(format!("A password pontosan 4 byte kell legyen! (kapott: {})", password.len()).into());
+  }
+  if pack.len() != 2 {
+    return Err(format!("A PACK pontosan 2 byte kell legyen! (kapott: {})", pack.len()).into());
+  }
+  
+  let uids = if uids.is_empty() {
+    let mut session = session.0.lock().unwrap();
+    let card = session.card()
+      .map_err(|e| CommandError::from(e).context("Csatlakozási hiba"))?;
+    let uid = Ntag216.read_uid(card)
+      .map_err(|e| CommandError::from(e).context("UID olvasási hiba"))?;
+    vec![vault::uid_to_hex(&uid)]
+  } else {
+    uids
+  };
+  
+  let mut guard = vault.0.lock().unwrap();
+  let opened = guard.as_mut()
+    .ok_or_else(|| "A tároló zárolva van! Előbb oldd fel a jelmondattal.".to_string())?;
+  opened.add(VaultEntry {
+    label: label.clone(),
+    uids,
+    password: [password[0], password[1], password[2], password[3]],
+    pack: [pack[0], pack[1]],
+  }).map_err(|e| CommandError::from(e).context("Tároló mentési hiba"))?;
+  Ok(format!("Bejegyzés elmentve: {}", label))
+}
+
+/// Bejegyzések listázása (password-ok nélkül)
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn vault_list(vault: tauri::State<VaultState>) -> Result<Vec<VaultEntrySummary>, CommandError> {
+  let guard = vault.0.lock().unwrap();
+  let opened = guard.as_ref()
+    .ok_or_else(|| "A tároló zárolva van! Előbb oldd fel a jelmondattal.".to_string())?;
+  Ok(opened.list())
+}
+
+/// Bejegyzés törlése
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn vault_remove(label: String, vault: tauri::State<VaultState>) -> Result<String, CommandError> {
+  let mut guard = vault.0.lock().unwrap();
+  let opened = guard.as_mut()
+    .ok_or_else(|| "A tároló zárolva van! Előbb oldd fel a jelmondattal.".to_string())?;
+  match opened.remove(&label).map_err(|e| CommandError::from(e).context("Tároló mentési hiba"))? {
+    true => Ok(format!("Bejegyzés törölve: {}", label)),
+    false => Err(CommandError::new("not_found", format!("Nincs ilyen bejegyzés: {}", label))),
   }
 }
 
 /// NFC olvasók listázása
 #[tauri::command]
-fn list_readers() -> Result<Vec<String>, String> {
-  let reader = NfcReader::new()
-    .map_err(|e| format!("NFC olvasó inicializálási hiba: {}", e))?;
+#[tracing::instrument(skip_all)]
+fn list_readers(session: tauri::State<SessionState>) -> Result<Vec<String>, CommandError> {
+  let mut session = session.0.lock().unwrap();
+  session.list_readers()
+    .map_err(|e| CommandError::from(e).context("Olvasók listázási hiba"))
+}
+
+fn settings_path(app: &tauri::AppHandle) -> Result<std::path::PathBuf, CommandError> {
+  app.path_resolver()
+    .app_data_dir()
+    .map(|dir| dir.join("settings.json"))
+    .ok_or_else(|| CommandError::new("error", "Nem található az alkalmazás adatkönyvtára"))
+}
+
+/// Olvasó kiválasztása név (vagy névrészlet) vagy sorszám alapján; None: az első olvasó.
+/// A kiválasztás mentésre kerül, és minden további műveletre érvényes.
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn select_reader(reader: Option<ReaderSelector>, app: tauri::AppHandle, session: tauri::State<SessionState>) -> Result<String, CommandError> {
+  let mut session = session.0.lock().unwrap();
+  let resolved = session.select_reader(reader.clone())
+    .map_err(|e| CommandError::from(e).context("Olvasó kiválasztási hiba"))?;
+
+  let path = settings_path(&app)?;
+  let mut settings = Settings::load(&path);
+  settings.set_selection(session.backend(), reader);
+  settings.save(&path)
+    .map_err(|e| CommandError::from(e).context("Beállítások mentési hiba"))?;
+
+  Ok(match resolved {
+    Some(name) => format!("Kiválasztott olvasó: {}", name),
+    None => "Az első elérhető olvasó lesz használva".to_string(),
+  })
+}
+
+/// Újrapróbálási szabály beállítása átmeneti (RF / timeout) hibákhoz; mentésre kerül
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn set_retry_policy(policy: RetryPolicy, app: tauri::AppHandle) -> Result<String, CommandError> {
+  nfc::transport::set_retry_policy(policy);
+  let policy = nfc::transport::retry_policy();
+  
+  let path = settings_path(&app)?;
+  let mut settings = Settings::load(&path);
+  settings.retry = policy;
+  settings.save(&path)
+    .map_err(|e| CommandError::from(e).context("Beállítások mentési hiba"))?;
+  
+  Ok(format!(
+    "Újrapróbálás: {} próbálkozás, {}-{} ms várakozás",
+    policy.max_attempts, policy.initial_backoff_ms, policy.max_backoff_ms
+  ))
+}
+
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn get_retry_policy() -> RetryPolicy {
+  nfc::transport::retry_policy()
+}
+
+/// Az aktív olvasó háttér és az ezen a gépen használható hátterek
+#[derive(Debug, Serialize)]
+struct BackendInfo {
+  active: Backend,
+  available: Vec<Backend>,
+}
+
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn get_backend(session: tauri::State<SessionState>) -> BackendInfo {
+  BackendInfo {
+    active: session.0.lock().unwrap().backend(),
+    available: Backend::available(),
+  }
+}
+
+/// Olvasó háttér váltása (PC/SC / libnfc); a háttérhez mentett olvasó kiválasztás is
+/// visszaáll. A választás mentésre kerül.
+#[tauri::command]
+#[tracing::instrument(skip_all, fields(backend = backend.label()))]
+fn set_backend(backend: Backend, app: tauri::AppHandle, session: tauri::State<SessionState>) -> Result<String, CommandError> {
+  backend.ensure_available()?;
+  
+  let path = settings_path(&app)?;
+  let mut settings = Settings::load(&path);
+  settings.backend = backend;
+  settings.save(&path)
+    .map_err(|e| CommandError::from(e).context("Beállítások mentési hiba"))?;
   
-  reader.list_readers()
-    .map_err(|e| format!("Olvasók listázási hiba: {}", e))
+  session.0.lock().unwrap().set_backend(backend, settings.selection(backend));
+  Ok(format!("Olvasó háttér: {}", backend.label()))
+}
+
+/// A jelenleg kiválasztott olvasó (None: az első elérhető olvasó)
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn selected_reader(session: tauri::State<SessionState>) -> Option<ReaderSelector> {
+  session.0.lock().unwrap().selection().cloned()
+}
+
+/// Auto mód indítása: a feladat minden újonnan felhelyezett címkén lefut.
+/// Visszafordíthatatlan feladatnál (read-only, zárolás) először megerősítő tokent ad,
+/// amely a referencia chip zárolási tervére és a futás címke korlátjára (`max_tags`) szól:
+/// más chipet vagy a korláton felüli címkét az auto mód nem zárol.
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn start_auto_mode(job: AutoJob, chip: Option<NtagChip>, max_tags: Option<u32>, confirmation_token: Option<String>, guard: tauri::State<ConfirmationGuard>, auto: tauri::State<AutoMode>, batch: tauri::State<BatchMode>) -> Result<IrreversibleResponse, CommandError> {
+  let mut confirmed_lock = None;
+  if job.is_irreversible() {
+    let max_tags = match max_tags {
+      Some(max_tags) if max_tags > 0 => max_tags,
+      _ => return Err(CommandError::new(
+        "limit_required",
+        "Zároló auto módhoz meg kell adni, legfeljebb hány címkét zárolhat a futás",
+      )),
+    };
+    let chip = chip.unwrap_or(NtagChip::Ntag216);
+    let request = match &job {
+      AutoJob::Lock { ranges, lock_config, cc_read_only, freeze_lock_bits } =>
+        lock_request(ranges.clone(), *lock_config, *cc_read_only, *freeze_lock_bits),
+      _ => LockRequest::read_only(chip),
+    };
+    let plan = nfc::lock::plan_lock(chip, &request)
+      .map_err(|e| CommandError::from(e).context("Zárolási terv hiba"))?;
+    let subject = Subject::Run { max_tags };
+    
+    match confirmation_token {
+      None => {
+        let challenge = guard.issue("auto_mode", subject, &plan);
+        return Ok(IrreversibleResponse {
+          executed: false,
+          message: format!(
+            "Megerősítés szükséges {} másodpercen belül!\n⚠️ Az auto mód legfeljebb {} felhelyezett {:?} címkét véglegesen zárol, a többit kihagyja.\nTerv:\n{}",
+            challenge.expires_in_secs,
+            max_tags,
+            chip,
+            plan.report()
+          ),
+          challenge: Some(challenge),
+        });
+      }
+      Some(token) => guard.redeem(&token, "auto_mode", &subject, &plan)
+        .map_err(|e| CommandError::from(e).context("Megerősítési hiba"))?,
+    }
+    confirmed_lock = Some(ConfirmedLock { plan, max_tags });
+  }
+  
+  if batch.is_running() {
+    return Err(CommandError::new("busy", "Batch kódolás fut, előbb állítsd le!"));
+  }
+  
+  info!("Auto mód indítása: {:?}", job);
+  auto.start(job, confirmed_lock);
+  Ok(IrreversibleResponse {
+    executed: true,
+    challenge: None,
+    message: "Auto mód elindítva. Helyezd fel a címkéket egymás után.".to_string(),
+  })
+}
+
+/// Auto mód leállítása, végső összesítéssel
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn stop_auto_mode(auto: tauri::State<AutoMode>) -> Result<String, CommandError> {
+  match auto.stop() {
+    Some(tally) => Ok(format!(
+      "Auto mód leállítva. Sikeres: {}, sikertelen: {}, kihagyott: {}",
+      tally.succeeded, tally.failed, tally.skipped
+    )),
+    None => Err(CommandError::new("not_running", "Az auto mód nem fut")),
+  }
+}
+
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn auto_mode_status(auto: tauri::State<AutoMode>) -> Option<AutoStatus> {
+  auto.status()
+}
+
+/// Auto mód: a beállított feladat végrehajtása az új címkén (a monitor szálán),
+/// az eredmény és a futó összesítés eseményként megy a frontendnek
+fn run_auto_job(app: &tauri::AppHandle, uid: &str) {
+  let auto = app.state::<AutoMode>();
+  let result = match auto.begin(uid) {
+    Ok(None) => return,
+    Ok(Some((job, confirmed_plan))) => {
+      info!("Auto mód feladat: {:?} → {}", job, uid);
+      let outcome = execute_auto_job(app, uid, job, confirmed_plan.as_ref());
+      match auto.finish(uid, outcome.map_err(|e| e.message)) {
+        Some(result) => result,
+        None => return,
+      }
+    }
+    Err(skipped) => skipped,
+  };
+  
+  info!("Auto mód eredmény: {:?}", result);
+  if let Err(e) = app.emit_all("auto-tag-result", result) {
+    warn!("Esemény küldési hiba: {}", e);
+  }
+}
+
+/// `confirmed_plan`: zároló feladatnál a futás indításakor megerősített terv
+fn execute_auto_job(app: &tauri::AppHandle, uid: &str, job: AutoJob, confirmed_plan: Option<&LockPlan>) -> Result<String, CommandError> {
+  // A monitor által látott címke van-e a munkamenet olvasóján
+  {
+    let session_state = app.state::<SessionState>();
+    let mut session = session_state.0.lock().unwrap();
+    let card = session.card()
+      .map_err(|e| CommandError::from(e).context("Csatlakozási hiba"))?;
+    let current = Ntag216.read_uid(card)
+      .map_err(|e| CommandError::from(e).context("UID olvasási hiba"))?;
+    if vault::uid_to_hex(&current) != uid {
+      return Err(CommandError::new("tag_changed", format!(
+        "A kiválasztott olvasón másik címke van ({}), nem a felhelyezett {}",
+        vault::uid_to_hex(&current), uid
+      )));
+    }
+  }
+  
+  // Gyártási futásban minden írást visszaolvasunk
+  match job {
+    AutoJob::WriteUrl { url } =>
+      write_url(url, None, None, Some(true), app.state(), app.state(), app.state()),
+    AutoJob::WriteText { text, language } =>
+      write_text(text, language, None, None, Some(true), app.state(), app.state(), app.state()),
+    AutoJob::WriteVcard { vcard } =>
+      write_vcard(vcard, None, None, Some(true), app.state(), app.state(), app.state()),
+    AutoJob::SetPassword { password, pack, auth_limit } =>
+      set_password(password, pack, auth_limit, app.state(), app.state(), app.state()),
+    AutoJob::SetReadOnly | AutoJob::Lock { .. } => match confirmed_plan {
+      Some(plan) => auto_lock(app, plan),
+      None => Err(CommandError::new("confirmation_required", "Zárolás auto módban csak megerősített tervvel")),
+    },
+  }
+}
+
+/// Zárolás auto módban a futás indításakor megerősített tervvel. Az `apply_lock` a címke
+/// chipjét is ellenőrzi: más chipen (más tervet igényelne) nem ír semmit.
+fn auto_lock(app: &tauri::AppHandle, plan: &LockPlan) -> Result<String, CommandError> {
+  write_with_feedback(&app.state::<SessionState>(), |card| {
+    Ntag216.apply_lock(card, plan)
+      .map_err(|e| CommandError::from(e).context("Zárolási hiba"))?;
+    Ok(format!("⚠️ Zárolás kész! VISSZAFORDÍTHATATLAN!\n{}", plan.report()))
+  })
+}
+
+/// Batch kódolás indítása: az N. felhelyezett (és sikeresen megírt) címkére az N. rekord
+/// kerül a sablon alapján ({uid}, {serial}, {row.mező}). Az eredmények a manifestbe kerülnek.
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn start_batch(request: BatchRequest, app: tauri::AppHandle, batch: tauri::State<BatchMode>, auto: tauri::State<AutoMode>) -> Result<BatchSummary, CommandError> {
+  if auto.status().is_some() {
+    return Err(CommandError::new("busy", "Auto mód fut, előbb állítsd le!"));
+  }
+  
+  let records = batch::parse_records(&request.records, request.format)
+    .map_err(|e| CommandError::from(e).context("Rekord beolvasási hiba"))?;
+  let manifest_path = match request.manifest_path {
+    Some(path) if !path.trim().is_empty() => std::path::PathBuf::from(path.trim()),
+    _ => {
+      let started = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+      app.path_resolver()
+        .app_data_dir()
+        .map(|dir| dir.join("manifests").join(format!("batch-{}.json", started)))
+        .ok_or_else(|| CommandError::new("error", "Nem található az alkalmazás adatkönyvtára"))?
+    }
+  };
+  
+  let spec = BatchSpec {
+    payload: request.payload,
+    template: request.template,
+    serial_start: request.serial_start.unwrap_or(1),
+    records,
+  };
+  info!("Batch indítása: {} rekord, manifest: {}", spec.records.len(), manifest_path.display());
+  batch.start(spec, &manifest_path)
+    .map_err(|e| CommandError::from(e).context("Batch indítási hiba"))
+}
+
+/// Batch leállítása, végső összesítéssel
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn stop_batch(batch: tauri::State<BatchMode>) -> Result<BatchSummary, CommandError> {
+  batch.stop()
+    .ok_or_else(|| CommandError::new("not_running", "Nem fut batch kódolás"))
+}
+
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn batch_status(batch: tauri::State<BatchMode>) -> Option<BatchSummary> {
+  batch.status()
+}
+
+/// Batch: a következő sor megírása az új címkére (a monitor szálán)
+fn run_batch_job(app: &tauri::AppHandle, uid: &str) {
+  let batch = app.state::<BatchMode>();
+  let result = match batch.begin(uid) {
+    BatchStep::Idle => return,
+    BatchStep::Write(task) => {
+      info!("Batch: {}. sor → {}", task.row + 1, uid);
+      let outcome = write_batch_task(app, uid, &task);
+      match batch.finish(uid, task, outcome.map_err(|e| e.message)) {
+        Some(result) => result,
+        None => return,
+      }
+    }
+    BatchStep::Skip(skipped) => skipped,
+  };
+  
+  info!("Batch eredmény: {:?}", result);
+  if let Err(e) = app.emit_all("batch-tag-result", result) {
+    warn!("Esemény küldési hiba: {}", e);
+  }
+}
+
+fn write_batch_task(app: &tauri::AppHandle, uid: &str, task: &BatchTask) -> Result<String, CommandError> {
+  let job = match &task.payload {
+    BatchPayload::Url => AutoJob::WriteUrl { url: task.content.clone() },
+    BatchPayload::Text { language } => AutoJob::WriteText { text: task.content.clone(), language: language.clone() },
+    BatchPayload::Vcard => AutoJob::WriteVcard { vcard: task.content.clone() },
+  };
+  execute_auto_job(app, uid, job, None)
+}
+
+/// Címke jelenlét lekérdezése (a UI ezzel ellenőrzi, hogy a címke még az olvasón van-e)
+#[tauri::command]
+#[tracing::instrument(skip_all)]
+fn card_status(session: tauri::State<SessionState>) -> Result<CardStatus, CommandError> {
+  let mut session = session.0.lock().unwrap();
+  let backend = session.backend();
+  let card = session.card().ok();
+  let driver = card.map(ReaderDriver::identify);
+  let target = card.and_then(|card| card.target());
+  let present = card.is_some();
+  Ok(CardStatus {
+    present,
+    reader: if present { session.reader_name() } else { None },
+    driver,
+    backend,
+    target,
+  })
 }
 
 fn main() {
-  println!("🚀 NTAG216 NFC Alkalmazás indítása...");
+  logging::init();
+  info!("NTAG216 NFC Alkalmazás indítása...");
   
   tauri::Builder::default()
     .invoke_handler(tauri::generate_handler![
@@ -469,14 +1386,90 @@ fn main() {
       set_password,
       remove_password,
       set_read_only,
+      plan_lock,
+      apply_lock,
       read_config,
+      read_tag_info,
+      acr122u_status,
+      acr122u_configure,
       clear_ndef,
       write_raw,
       read_raw,
-      list_readers
+      list_readers,
+      card_status,
+      select_reader,
+      selected_reader,
+      set_retry_policy,
+      get_retry_policy,
+      start_auto_mode,
+      stop_auto_mode,
+      auto_mode_status,
+      start_batch,
+      stop_batch,
+      batch_status,
+      select_key_profile,
+      clear_key_profile,
+      vault_create,
+      vault_unlock,
+      vault_lock,
+      vault_add,
+      vault_list,
+      vault_remove,
+      get_backend,
+      set_backend,
+      transceive_raw,
+      console_history,
+      clear_console_history,
+      get_expert_mode,
+      set_expert_mode,
+      start_trace,
+      stop_trace,
+      trace_status,
+      replay_trace,
+      get_logs
     ])
-    .setup(|_app| {
-      println!("✅ Tauri alkalmazás inicializálva");
+    .manage(SessionState::default())
+    .manage(MonitorState::default())
+    .manage(AutoMode::default())
+    .manage(BatchMode::default())
+    .manage(KeyProfileState::default())
+    .manage(VaultState::default())
+    .manage(ConfirmationGuard::default())
+    .manage(ExpertConsole::default())
+    .setup(|app| {
+      // Mentett olvasó kiválasztás visszaállítása
+      if let Some(dir) = app.path_resolver().app_data_dir() {
+        let log_path = dir.join("logs").join("nfc.log");
+        match logging::set_log_file(&log_path) {
+          Ok(()) => info!("Log fájl: {}", log_path.display()),
+          Err(e) => warn!("A log fájl nem nyitható meg: {:#}", e),
+        }
+        let settings = Settings::load(&dir.join("settings.json"));
+        let selection = settings.selection(settings.backend);
+        if let Some(reader) = &selection {
+          info!("Mentett olvasó kiválasztás ({}): {:?}", settings.backend.label(), reader);
+        }
+        app.state::<SessionState>().0.lock().unwrap().set_backend(settings.backend, selection);
+        nfc::transport::set_retry_policy(settings.retry);
+        app.state::<ExpertConsole>().set_enabled(settings.expert_mode);
+      }
+      // Címke monitor: olvasó és címke események a frontendnek
+      let handle = app.handle();
+      match CardMonitor::start(move |event: MonitorEvent| {
+        info!("{}: {:?}", event.name(), event);
+        if let Err(e) = handle.emit_all(event.name(), event.clone()) {
+          warn!("Esemény küldési hiba: {}", e);
+        }
+        if let MonitorEvent::CardInserted { uid: Some(uid), .. } = &event {
+          run_auto_job(&handle, uid);
+          run_batch_job(&handle, uid);
+        }
+      }) {
+        Ok(monitor) => *app.state::<MonitorState>().0.lock().unwrap() = Some(monitor),
+        Err(e) => warn!("A címke monitor nem indult el: {}", e),
+      }
+
+      info!("Tauri alkalmazás inicializálva");
       Ok(())
     })
     .run(tauri::generate_context!())
@@ -0,0 +1,68 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use tracing::warn;
+
+use crate::nfc::{Backend, ReaderSelector, RetryPolicy};
+
+/// Újraindítás után is megmaradó beállítások (settings.json az alkalmazás adatkönyvtárában)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Settings {
+    /// Az olvasó háttér (PC/SC vagy libnfc)
+    #[serde(default)]
+    pub backend: Backend,
+    /// A kiválasztott olvasó (None: az első elérhető olvasó)
+    #[serde(default)]
+    pub reader: Option<ReaderSelector>,
+    /// A kiválasztott libnfc eszköz connstring-je vagy sorszáma (None: alapértelmezett eszköz)
+    #[serde(default)]
+    pub libnfc_device: Option<ReaderSelector>,
+    /// Újrapróbálás átmeneti kommunikációs hibáknál
+    #[serde(default)]
+    pub retry: RetryPolicy,
+    /// Szakértői mód: nyers natív parancsok küldése a konzolból
+    #[serde(default)]
+    pub expert_mode: bool,
+}
+
+impl Settings {
+    /// A háttérhez mentett olvasó kiválasztás
+    pub fn selection(&self, backend: Backend) -> Option<ReaderSelector> {
+        match backend {
+            Backend::Pcsc => self.reader.clone(),
+            Backend::Libnfc => self.libnfc_device.clone(),
+        }
+    }
+
+    pub fn set_selection(&mut self, backend: Backend, selection: Option<ReaderSelector>) {
+        match backend {
+            Backend::Pcsc => self.reader = selection,
+            Backend::Libnfc => self.libnfc_device = selection,
+        }
+    }
+
+    /// Beállítások betöltése; hiányzó vagy sérült fájl esetén az alapértelmezés
+    pub fn load(path: &Path) -> Self {
+        let Ok(content) = fs::read(path) else {
+            return Settings::default();
+        };
+        match serde_json::from_slice(&content) {
+            Ok(settings) => settings,
+            Err(e) => {
+                warn!("Sérült beállítás fájl ({}), alapértelmezések használata: {}", path.display(), e);
+                Settings::default()
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Nem sikerült létrehozni a könyvtárat: {}", parent.display()))?;
+        }
+        fs::write(path, serde_json::to_vec_pretty(self)?)
+            .with_context(|| format!("Nem sikerült írni a beállításokat: {}", path.display()))?;
+        Ok(())
+    }
+}
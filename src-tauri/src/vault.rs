@@ -0,0 +1,247 @@
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::{Context, Result};
+use argon2::Argon2;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Jelszóval védett, titkosított tároló a címkék password-jeihez
+///
+/// Kulcs: Argon2id(passphrase, salt), titkosítás: AES-256-GCM.
+/// A fájl csak a salt-ot, a nonce-ot és a titkosított bejegyzéseket tartalmazza.
+pub struct Vault {
+    path: PathBuf,
+    salt: Vec<u8>,
+    key: [u8; 32],
+    entries: Vec<VaultEntry>,
+}
+
+/// Egy bejegyzés: egy címke (1 UID) vagy címke csoport (több UID) password-je és PACK-ja
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultEntry {
+    pub label: String,
+    pub uids: Vec<String>,
+    pub password: [u8; 4],
+    pub pack: [u8; 2],
+}
+
+/// Bejegyzés listázáshoz (password nélkül)
+#[derive(Debug, Clone, Serialize)]
+pub struct VaultEntrySummary {
+    pub label: String,
+    pub uids: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct VaultFile {
+    version: u8,
+    salt: Vec<u8>,
+    nonce: Vec<u8>,
+    ciphertext: Vec<u8>,
+}
+
+const VAULT_VERSION: u8 = 1;
+
+impl Vault {
+    /// Új, üres tároló létrehozása; a meglévő tárolót nem írja felül
+    pub fn create(path: &Path, passphrase: &str) -> Result<Self> {
+        if path.exists() {
+            anyhow::bail!("A tároló már létezik: {}", path.display());
+        }
+        let mut salt = [0u8; 16];
+        OsRng.fill_bytes(&mut salt);
+        let vault = Vault {
+            path: path.to_path_buf(),
+            salt: salt.to_vec(),
+            key: derive_key(passphrase, &salt)?,
+            entries: Vec::new(),
+        };
+        vault.save()?;
+        Ok(vault)
+    }
+
+    /// Meglévő tároló megnyitása. Hiányzó fájlnál hiba: elgépelt útvonal vagy törölt tároló
+    /// esetén nem hozunk létre csendben egy üreset (azt csak a `create` teszi).
+    pub fn open(path: &Path, passphrase: &str) -> Result<Self> {
+        if !path.exists() {
+            anyhow::bail!("A tároló nem létezik: {} (előbb hozd létre)", path.display());
+        }
+
+        let content = fs::read(path)
+            .with_context(|| format!("Nem sikerült olvasni a tárolót: {}", path.display()))?;
+        let file: VaultFile = serde_json::from_slice(&content)
+            .context("Sérült tároló fájl")?;
+        if file.version != VAULT_VERSION {
+            anyhow::bail!("Nem támogatott tároló verzió: {}", file.version);
+        }
+        if file.nonce.len() != 12 {
+            anyhow::bail!("Sérült tároló fájl: érvénytelen nonce");
+        }
+
+        let key = derive_key(passphrase, &file.salt)?;
+        let cipher = Aes256Gcm::new_from_slice(&key)
+            .map_err(|_| anyhow::anyhow!("Érvénytelen kulcs hossz"))?;
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(&file.nonce), file.ciphertext.as_ref())
+            .map_err(|_| anyhow::anyhow!("Hibás jelmondat vagy sérült tároló"))?;
+        let entries = serde_json::from_slice(&plaintext)
+            .context("Sérült tároló tartalom")?;
+
+        Ok(Vault {
+            path: path.to_path_buf(),
+            salt: file.salt,
+            key,
+            entries,
+        })
+    }
+
+    /// Bejegyzés hozzáadása (az azonos nevű bejegyzést lecseréli)
+    pub fn add(&mut self, entry: VaultEntry) -> Result<()> {
+        self.entries.retain(|e| e.label != entry.label);
+        self.entries.push(entry);
+        self.save()
+    }
+
+    /// Bejegyzés törlése név alapján
+    pub fn remove(&mut self, label: &str) -> Result<bool> {
+        let before = self.entries.len();
+        self.entries.retain(|e| e.label != label);
+        let removed = self.entries.len() != before;
+        if removed {
+            self.save()?;
+        }
+        Ok(removed)
+    }
+
+    pub fn list(&self) -> Vec<VaultEntrySummary> {
+        self.entries
+            .iter()
+            .map(|e| VaultEntrySummary {
+                label: e.label.clone(),
+                uids: e.uids.clone(),
+            })
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Password keresése UID alapján
+    pub fn lookup(&self, uid: &[u8]) -> Option<&VaultEntry> {
+        let uid_hex = uid_to_hex(uid);
+        self.entries
+            .iter()
+            .find(|e| e.uids.iter().any(|u| normalize_uid(u) == uid_hex))
+    }
+
+    fn save(&self) -> Result<()> {
+        let cipher = Aes256Gcm::new_from_slice(&self.key)
+            .map_err(|_| anyhow::anyhow!("Érvénytelen kulcs hossz"))?;
+        let mut nonce = [0u8; 12];
+        OsRng.fill_bytes(&mut nonce);
+
+        let plaintext = serde_json::to_vec(&self.entries)?;
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), plaintext.as_ref())
+            .map_err(|_| anyhow::anyhow!("Titkosítási hiba"))?;
+
+        let file = VaultFile {
+            version: VAULT_VERSION,
+            salt: self.salt.clone(),
+            nonce: nonce.to_vec(),
+            ciphertext,
+        };
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Nem sikerült létrehozni a könyvtárat: {}", parent.display()))?;
+        }
+        write_atomic(&self.path, &serde_json::to_vec(&file)?)
+            .with_context(|| format!("Nem sikerült írni a tárolót: {}", self.path.display()))?;
+        Ok(())
+    }
+}
+
+/// Írás ideiglenes fájlba, majd átnevezés: összeomlás vagy teli lemez esetén is a régi vagy
+/// az új tartalom marad meg, sosem egy félig kiírt (a tároló és a batch manifest is így íródik)
+pub(crate) fn write_atomic(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+
+    let result = File::create(&temp_path)
+        .and_then(|mut file| {
+            file.write_all(content)?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&temp_path, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<[u8; 32]> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow::anyhow!("Kulcs származtatási hiba: {}", e))?;
+    Ok(key)
+}
+
+/// UID hex formátumban (nagybetűs, elválasztók nélkül)
+pub fn uid_to_hex(uid: &[u8]) -> String {
+    uid.iter().map(|b| format!("{:02X}", b)).collect()
+}
+
+fn normalize_uid(uid: &str) -> String {
+    uid.chars()
+        .filter(|c| c.is_ascii_hexdigit())
+        .collect::<String>()
+        .to_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("nfc-vault-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir.join("vault.json")
+    }
+
+    #[test]
+    fn open_missing_vault_is_an_error() {
+        let path = temp_path("missing");
+        assert!(Vault::open(&path, "jelmondat").is_err());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn create_then_open_round_trip() {
+        let path = temp_path("roundtrip");
+        let mut vault = Vault::create(&path, "jelmondat").unwrap();
+        vault
+            .add(VaultEntry {
+                label: "raktar".to_string(),
+                uids: vec!["04:A1:B2:C3:D4:E5:80".to_string()],
+                password: [0x12, 0x34, 0x56, 0x78],
+                pack: [0x80, 0x80],
+            })
+            .unwrap();
+        assert!(!path.with_file_name("vault.json.tmp").exists());
+
+        let reopened = Vault::open(&path, "jelmondat").unwrap();
+        let entry = reopened.lookup(&[0x04, 0xA1, 0xB2, 0xC3, 0xD4, 0xE5, 0x80]).unwrap();
+        assert_eq!(entry.password, [0x12, 0x34, 0x56, 0x78]);
+        assert!(Vault::open(&path, "rossz").is_err());
+        assert!(Vault::create(&path, "jelmondat").is_err());
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}